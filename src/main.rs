@@ -3,19 +3,164 @@ use bevy::{prelude::*};
 use bevy_ui_scroll::background_scene::*;
 use bevy_ui_scroll::scroll::components::*;
 use bevy_ui_scroll::scroll::UiScrollPlugin;
-use bevy_ui_scroll::scroll::scroll_container::ScrollContainerWidget;
+use bevy_ui_scroll::scroll::scroll_container::{
+    ScrollContainerWidget, ScrollFrameParent, ScrollViewport,
+};
+use bevy_ui_scroll::scroll::scroll_content::ScrollContentElement;
+#[cfg(feature = "ron")]
+use bevy_ui_scroll::scroll::scroll_asset::ScrollContainerAsset;
 
 fn main() {
-    App::new()
+    let mut app = App::new();
+    app
         .add_plugins(DefaultPlugins)
         .add_plugin(UiScrollPlugin)
         .add_startup_system(setup_scene)
         .add_startup_system(spawn_scroll_container)
-        .run();
+        .add_system(fill_scroll_content);
+
+    #[cfg(feature = "ron")]
+    app.add_system(spawn_scroll_asset_container);
+
+    app.run();
 }
 
+// A vertical list with a horizontal carousel mounted among its rows. Vertical wheel input over
+// the carousel has nowhere to go in it, so it chains out to the list.
+#[derive(Component)]
+struct ScrollList;
+
+#[derive(Component)]
+struct ScrollListSlot;
+
 fn spawn_scroll_container(mut commands: Commands) {
-    commands.spawn(ScrollContainerWidget {
-        scroll_direction: ScrollDirection::Both, 
+    // Places the list's frame, centred vertically and in from the left of the window.
+    let list_slot = commands.spawn(NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+            align_items: AlignItems::Center,
+            padding: UiRect {
+                left: Val::Percent(10.0),
+                ..default()
+            },
+            ..default()
+        },
+        ..default()
+    }).insert(ScrollListSlot).id();
+
+    commands.spawn((
+        ScrollContainerWidget {
+            scroll_direction: ScrollDirection::Vertical,
+            overscroll_behavior: OverscrollBehavior::Chain,
+            camera: None,
+        },
+        ScrollFrameParent(list_slot),
+        ScrollList,
+    ));
+}
+
+// A container described in assets/scroll/example.scroll.ron, shown beside the list when the
+// demo is run with `--features ron`.
+#[cfg(feature = "ron")]
+fn spawn_scroll_asset_container(
+    q_new_slots: Query<Entity, Added<ScrollListSlot>>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    for slot in q_new_slots.iter() {
+        let asset: Handle<ScrollContainerAsset> = asset_server.load("scroll/example.scroll.ron");
+        commands.spawn((asset, ScrollFrameParent(slot)));
+    }
+}
+
+fn fill_scroll_content(
+    q_new_content: Query<(&ScrollContentElement, &Parent), Added<ScrollContentElement>>,
+    q_lists: Query<(), With<ScrollList>>,
+    mut commands: Commands,
+) {
+    for (element, container) in q_new_content.iter() {
+        if !q_lists.contains(container.get()) {
+            spawn_filler_row(&mut commands, element.scroll_content, 1, 31);
+            continue;
+        }
+
+        for n in 1..51 {
+            spawn_filler_row(&mut commands, element.scroll_content, n, 11);
+
+            if n == 5 {
+                let carousel_slot = commands.spawn(NodeBundle::default()).id();
+                commands
+                    .entity(element.scroll_content)
+                    .add_child(carousel_slot);
+
+                // Owned by the list, so despawning the list takes the carousel with it.
+                let carousel = commands.spawn((
+                    ScrollContainerWidget {
+                        scroll_direction: ScrollDirection::Horizontal,
+                        overscroll_behavior: OverscrollBehavior::Chain,
+                        camera: None,
+                    },
+                    ScrollViewport {
+                        width: Val::Px(400.0),
+                        height: Val::Px(80.0),
+                    },
+                    ScrollFrameParent(carousel_slot),
+                )).id();
+                commands.entity(container.get()).add_child(carousel);
+            }
+        }
+    }
+}
+
+fn spawn_filler_row(commands: &mut Commands, scroll_content: Entity, n: u16, length: u16) {
+    let scroll_content_row = commands.spawn(NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            ..default()
+        },
+        ..default()
+    }).id();
+
+    commands
+        .entity(scroll_content)
+        .add_child(scroll_content_row);
+
+    for i in 1..length {
+        generate_filler_content(commands, scroll_content_row, n * i, 51.0 * 51.0);
+    }
+}
+
+fn generate_filler_content(commands: &mut Commands, parent: Entity, number: u16, biggest_number: f32) {
+    let fixed_tick_node = NodeBundle {
+        style: Style {
+            min_size: Size {
+                width: Val::Px(40.0),
+                height: Val::Px(40.0),
+            },
+            size: Size {
+                width: Val::Px(40.0),
+                height: Val::Px(40.0),
+            },
+            margin: UiRect {
+                top: Val::Px(2.0),
+                left: Val::Px(2.0),
+                ..default()
+            },
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ..default()
+    };
+
+    commands.entity(parent).with_children(|container_panel_p| {
+        container_panel_p
+            .spawn(fixed_tick_node)
+            .insert(BackgroundColor(Color::rgb(
+                0.5,
+                1.0 - (f32::from(number) / biggest_number),
+                f32::from(number) / biggest_number,
+            )));
     });
-}
\ No newline at end of file
+}
+
@@ -2,7 +2,8 @@ use std::marker::PhantomData;
 
 use bevy::prelude::*;
 
-#[derive(Copy, Clone, PartialEq, Debug, Default)]
+#[derive(Copy, Clone, PartialEq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub enum ScrollDirection {
     #[default]
     Vertical,
@@ -11,13 +12,120 @@ pub enum ScrollDirection {
     Neither,
 }
 
-#[derive(Default, Clone, Copy, Debug)]
+impl ScrollDirection {
+    pub fn scrolls_x(&self) -> bool {
+        matches!(self, ScrollDirection::Horizontal | ScrollDirection::Both)
+    }
+
+    pub fn scrolls_y(&self) -> bool {
+        matches!(self, ScrollDirection::Vertical | ScrollDirection::Both)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum OverscrollBehavior {
+    /// Leftover distance is passed on to the enclosing scroll container.
+    #[default]
+    Chain,
+    /// Leftover distance on an axis this container scrolls is dropped at its limits.
+    /// Axes it doesn't scroll at all still reach the enclosing container.
+    Contain,
+    /// The container swallows all wheel input over it.
+    None,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub enum ScrollBarOrientation {
     #[default]
     Vertical,
     Horizontal,
 }
 
+// Start is the left or top edge of the viewport, End the right or bottom one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollBarSide {
+    Start,
+    #[default]
+    End,
+}
+
+// Outside bars take their own space next to the viewport, inset bars are drawn over it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollBarOverlap {
+    #[default]
+    Outside,
+    Inset,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollBarPlacement {
+    pub side: ScrollBarSide,
+    pub overlap: ScrollBarOverlap,
+}
+
+// `Always` keeps the track even when there's nothing to scroll, `Auto` hides the whole bar
+// then, and `Never` hides it for good while still allowing other scroll input.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollBarVisibility {
+    Always,
+    #[default]
+    Auto,
+    Never,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollPartState {
+    #[default]
+    Idle,
+    Hovered,
+    Pressed,
+    Disabled,
+}
+
+// How a bar sizes its handle. `Proportional` follows the visible share of the content within
+// the bar's min/max handle lengths, `Fixed` always uses the bar's `handle_length`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollHandleSizing {
+    #[default]
+    Proportional,
+    Fixed,
+}
+
+impl ScrollBarOrientation {
+    pub fn axis(&self) -> ScrollAxis {
+        match self {
+            ScrollBarOrientation::Vertical => ScrollAxis::Vertical,
+            ScrollBarOrientation::Horizontal => ScrollAxis::Horizontal,
+        }
+    }
+}
+
+// Widgets spawned by a container read their configuration from it, so a component on either
+// the widget or its parent applies.
+pub fn widget_or_parent<'a, T: Component>(
+    entity: Entity,
+    q_config: &'a Query<&T>,
+    q_parents: &Query<&Parent>,
+) -> Option<&'a T> {
+    q_config
+        .get(entity)
+        .or_else(|_| {
+            q_parents
+                .get(entity)
+                .and_then(|parent| q_config.get(parent.get()))
+        })
+        .ok()
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct ControlledByElement<C: Component> {
     pub element: Entity,
@@ -32,14 +140,96 @@ pub struct PressInput {
 #[derive(Clone, Copy, Default, Debug)]
 pub struct ScrollInput {
     pub distance: Vec2,
+    pub source: ScrollSource,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollSource {
+    Wheel,
+    Drag,
+    #[default]
+    Programmatic,
+    Autoscroll,
+    // The offset was clamped because the content or viewport changed size.
+    Layout,
+}
+
+// Shapes a 0 to 1 progress, for autoscroll speed and linked properties.
+#[derive(Copy, Clone, PartialEq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollEasing {
+    #[default]
+    Linear,
+    CubicBezier { p1: Vec2, p2: Vec2 },
+}
+
+impl ScrollEasing {
+    pub fn ease(&self, progress: f32) -> f32 {
+        match self {
+            ScrollEasing::Linear => progress,
+            ScrollEasing::CubicBezier { p1, p2 } => {
+                CubicBezierEasing::new(*p1, *p2).ease(progress)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollAxis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollEdge {
+    Start,
+    End,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Scrolled {
+    pub old_offset: Vec2,
+    pub new_offset: Vec2,
+    pub source: ScrollSource,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ReachedEdge {
+    pub axis: ScrollAxis,
+    pub edge: ScrollEdge,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ScrollTo {
+    Offset(Vec2),
+    Normalized(Vec2),
+}
+
+// Scrolls a single axis of a container, leaving the other where it is.
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollAxisTo {
+    pub axis: ScrollAxis,
+    pub normalized: f32,
+    pub source: ScrollSource,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct OverflowChanged {
+    pub overflowing: BVec2,
 }
 
 pub struct ScrollIndicatorEnabled {
     pub position: Vec2,
+    pub window: Entity,
 }
 
 pub struct ScrollIndicatorDisabled;
 
+pub struct ScrollIndicatorToggled;
+
 pub struct UiEvent<C: Component, A> {
     pub widget: Entity,
     pub action: A,
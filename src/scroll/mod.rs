@@ -1,14 +1,31 @@
 pub mod components;
+#[cfg(feature = "ron")]
+pub mod scroll_asset;
 pub mod scroll_bar;
 pub mod scroll_content;
 pub mod scroll_indicator;
+pub mod scroll_linked;
+pub mod scroll_marker;
+pub mod scroll_minimap;
 pub mod scroll_container;
+pub mod scroll_fade;
+pub mod scroll_persistence;
+pub mod scroll_skin;
+pub mod scroll_theme;
 pub mod styles;
 
 use scroll_bar::*;
 use scroll_content::*;
 use scroll_indicator::*;
+use scroll_linked::*;
+use scroll_marker::*;
+use scroll_minimap::*;
 use scroll_container::*;
+use scroll_fade::*;
+use scroll_persistence::*;
+use scroll_skin::*;
+use scroll_theme::*;
+use components::*;
 
 #[derive(Hash, Debug, Eq, PartialEq, Clone, Copy, SystemSet)]
 pub enum ScrollSystemSet {
@@ -18,12 +35,20 @@ pub enum ScrollSystemSet {
     Constrain,
     Propagate,
     Extract,
+    PostLayout,
 }
 
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PropagateSchedule;
 
-use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+#[derive(Resource, Default)]
+pub struct ScrollLayoutPass {
+    pub resized: bool,
+}
+
+const MAX_PROPAGATE_PASSES: usize = 4;
+
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*, transform::TransformSystem, ui::{flex_node_system, UiSystem}};
 
 pub struct UiScrollPlugin;
 
@@ -32,6 +57,23 @@ impl Plugin for UiScrollPlugin {
         use ScrollSystemSet::*;
 
         app
+            .register_type::<Option<Entity>>()
+            .register_type::<Option<Vec2>>()
+            .register_type::<ScrollDirection>()
+            .register_type::<OverscrollBehavior>()
+            .register_type::<ScrollSource>()
+            .register_type::<Option<ScrollSource>>()
+            .register_type::<ScrollEasing>()
+            .register_type::<ScrollAxis>()
+            .register_type::<ScrollEdge>()
+            .register_type::<ScrollPartState>()
+            .register_type::<ScrollBarOrientation>()
+            .register_type::<ScrollBarSide>()
+            .register_type::<ScrollBarOverlap>()
+            .register_type::<ScrollBarPlacement>()
+            .register_type::<ScrollBarVisibility>()
+            .register_type::<ScrollHandleSizing>()
+            .init_resource::<ScrollLayoutPass>()
             .add_schedule(PropagateSchedule, Schedule::new())
             .configure_sets((
                 Create,
@@ -41,31 +83,60 @@ impl Plugin for UiScrollPlugin {
                 Propagate,
                 Extract
             ).chain())
-            // .add_system(
-            //     run_ui_propagate_schedule.in_set(Propagate)
-            // )
+            .configure_set(Extract.before(UiSystem::Flex))
+            .configure_set(
+                PostLayout
+                    .in_base_set(CoreSet::PostUpdate)
+                    .after(UiSystem::Flex)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_system(
+                run_ui_propagate_schedule
+                    .in_set(PostLayout)
+                    .before(offset_scroll_content)
+            )
+            .edit_schedule(PropagateSchedule, |schedule| {
+                schedule
+                    .configure_sets((Update, Constrain, Propagate, Extract).chain())
+                    .configure_set(Constrain.run_if(scroll_layout_resized))
+                    .configure_set(Propagate.run_if(scroll_layout_resized))
+                    .configure_set(Extract.run_if(scroll_layout_resized))
+                    .add_system(
+                        flex_node_system
+                            .after(Extract)
+                            .run_if(scroll_layout_resized)
+                    );
+            })
             .add_plugin(ScrollBarPlugin)
             .add_plugin(ScrollContentPlugin)
             .add_plugin(ScrollIndicatorPlugin)
-            .add_plugin(ScrollContainerPlugin);
+            .add_plugin(ScrollLinkedPlugin)
+            .add_plugin(ScrollMarkerPlugin)
+            .add_plugin(ScrollMinimapPlugin)
+            .add_plugin(ScrollContainerPlugin)
+            .add_plugin(ScrollFadePlugin)
+            .add_plugin(ScrollPersistencePlugin)
+            .add_plugin(ScrollSkinPlugin)
+            .add_plugin(ScrollThemePlugin);
+
+        #[cfg(feature = "ron")]
+        app.add_plugin(scroll_asset::ScrollAssetPlugin);
     }
 }
 
+// Bevy lays the UI out after the scroll sets have run, so sizes they read can be a frame old.
+// Once layout is done the sizing dependent systems run again, relaying out in between, until
+// no scroll node changes size.
 pub fn run_ui_propagate_schedule(world: &mut World) {
-    // let mut schedules = world.resource_mut::<Schedules>();
+    for _ in 0..MAX_PROPAGATE_PASSES {
+        world.run_schedule(PropagateSchedule);
 
-    // let propagate_schedule = schedules
-    //     .get(&PropagateSchedule)
-    //     .unwrap_or_else(|| panic!(""));
-
-    // for system in propagate_schedule.graph().systems() {
-    //     system.1.get_last_change_tick();
-    // }
+        if !world.resource::<ScrollLayoutPass>().resized {
+            break;
+        }
+    }
+}
 
-    // let before_schedule_tick = world.change_tick();
-    //    world.run_schedule(PropagateSchedule);
-    // let after_schedule_tick = world.change_tick();
-    // for entity in world.iter_entities() {
-    //     //entity.get_change_ticks::<ScrollBarWidget>().unwrap().is_changed(before_schedule_tick, after_schedule_tick);
-    // }
+pub fn scroll_layout_resized(layout_pass: Res<ScrollLayoutPass>) -> bool {
+    layout_pass.resized
 }
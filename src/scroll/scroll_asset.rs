@@ -0,0 +1,211 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use super::components::*;
+use super::scroll_bar::ScrollBarSettings;
+use super::scroll_container::{
+    ScrollContainerElement, ScrollContainerWidget, ScrollMetrics, ScrollViewport,
+};
+use super::scroll_content::ScrollContentElement;
+use super::scroll_persistence::{ScrollPersistenceKey, ScrollPositionRestored};
+use super::scroll_theme::ScrollTheme;
+use super::ScrollSystemSet;
+
+// A scroll container described in a `.scroll.ron` file. Spawn an entity with a
+// `Handle<ScrollContainerAsset>` and the container is built from it once the asset has loaded,
+// and rebuilt whenever the file changes.
+#[derive(Deserialize, TypeUuid, Clone, Debug, Default)]
+#[uuid = "5b0e3c8e-8f6b-4a47-9a77-54d0a1c2e6f3"]
+#[serde(default)]
+pub struct ScrollContainerAsset {
+    pub scroll_direction: ScrollDirection,
+    pub overscroll_behavior: OverscrollBehavior,
+    pub viewport: ScrollViewport,
+    pub bar: ScrollBarSettings,
+    pub theme: ScrollTheme,
+    pub content: Vec<ScrollContentNode>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ScrollContentNode {
+    pub width: Val,
+    pub height: Val,
+    pub margin: Val,
+    pub flex_direction: FlexDirection,
+    pub color: Option<Color>,
+    pub children: Vec<ScrollContentNode>,
+}
+
+// Marks a container whose content nodes have been spawned from its asset.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ScrollAssetContentSpawned;
+
+#[derive(Default)]
+pub struct ScrollContainerAssetLoader;
+
+impl AssetLoader for ScrollContainerAssetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let asset = ron::de::from_bytes::<ScrollContainerAsset>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(asset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scroll.ron"]
+    }
+}
+
+pub struct ScrollAssetPlugin;
+
+impl Plugin for ScrollAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset::<ScrollContainerAsset>()
+            .init_asset_loader::<ScrollContainerAssetLoader>()
+            .add_system(
+                reload_scroll_containers
+                    .in_set(ScrollSystemSet::Create)
+                    .before(spawn_scroll_containers_from_assets),
+            )
+            .add_system(spawn_scroll_containers_from_assets.in_set(ScrollSystemSet::Create))
+            .add_system(spawn_scroll_asset_content.in_set(ScrollSystemSet::Create));
+    }
+}
+
+pub fn spawn_scroll_containers_from_assets(
+    q_pending_containers: Query<
+        (Entity, &Handle<ScrollContainerAsset>),
+        Without<ScrollContainerWidget>,
+    >,
+    assets: Res<Assets<ScrollContainerAsset>>,
+    mut commands: Commands,
+) {
+    for (entity, handle) in q_pending_containers.iter() {
+        let Some(asset) = assets.get(handle) else {
+            continue;
+        };
+
+        commands.entity(entity).insert((
+            ScrollContainerWidget {
+                scroll_direction: asset.scroll_direction,
+                overscroll_behavior: asset.overscroll_behavior,
+                camera: None,
+            },
+            asset.viewport.clone(),
+            asset.bar.clone(),
+            asset.theme.clone(),
+        ));
+    }
+}
+
+pub fn spawn_scroll_asset_content(
+    q_containers: Query<
+        (Entity, &Handle<ScrollContainerAsset>, &ScrollContainerElement),
+        Without<ScrollAssetContentSpawned>,
+    >,
+    q_content_elements: Query<&ScrollContentElement>,
+    assets: Res<Assets<ScrollContainerAsset>>,
+    mut commands: Commands,
+) {
+    for (entity, handle, container_element) in q_containers.iter() {
+        let Ok(content_element) = q_content_elements.get(container_element.scroll_content_widget) else {
+            continue;
+        };
+        let Some(asset) = assets.get(handle) else {
+            continue;
+        };
+
+        for node in &asset.content {
+            spawn_content_node(&mut commands, content_element.scroll_content, node);
+        }
+
+        commands.entity(entity).insert(ScrollAssetContentSpawned);
+    }
+}
+
+fn spawn_content_node(commands: &mut Commands, parent: Entity, node: &ScrollContentNode) {
+    let size = Size::new(node.width, node.height);
+    let entity = commands
+        .spawn(NodeBundle {
+            style: Style {
+                min_size: size,
+                size,
+                margin: UiRect::all(node.margin),
+                flex_direction: node.flex_direction,
+                ..default()
+            },
+            background_color: BackgroundColor(node.color.unwrap_or(Color::NONE)),
+            ..default()
+        })
+        .id();
+
+    commands.entity(parent).add_child(entity);
+
+    for child in &node.children {
+        spawn_content_node(commands, entity, child);
+    }
+}
+
+// Tears down everything built for a container whose asset changed; the spawn systems then
+// rebuild it through the usual init path.
+pub fn reload_scroll_containers(
+    mut asset_events: EventReader<AssetEvent<ScrollContainerAsset>>,
+    q_containers: Query<(
+        Entity,
+        &Handle<ScrollContainerAsset>,
+        Option<&ScrollContainerElement>,
+        Option<&ScrollPersistenceKey>,
+    )>,
+    mut commands: Commands,
+) {
+    for event in asset_events.iter() {
+        let AssetEvent::Modified { handle: modified } = event else {
+            continue;
+        };
+
+        for (entity, handle, container_element, persistence_key) in q_containers.iter() {
+            if handle != modified {
+                continue;
+            }
+
+            // The frame holds the bar, corner and wrapper nodes.
+            if let Some(container_element) = container_element {
+                for bar_widget in &container_element.scroll_bar_widgets {
+                    commands.entity(*bar_widget).despawn_recursive();
+                }
+                commands
+                    .entity(container_element.scroll_content_widget)
+                    .despawn_recursive();
+                commands.entity(container_element.scroll_frame).despawn_recursive();
+            }
+
+            commands.entity(entity).remove::<(
+                ScrollContainerWidget,
+                ScrollContainerElement,
+                ScrollMetrics,
+                ScrollAssetContentSpawned,
+                ScrollPositionRestored,
+            )>();
+
+            // Re-inserting the key saves the current position so it's restored after the rebuild.
+            if let Some(persistence_key) = persistence_key {
+                commands
+                    .entity(entity)
+                    .remove::<ScrollPersistenceKey>()
+                    .insert(persistence_key.clone());
+            }
+        }
+    }
+}
@@ -1,54 +1,213 @@
 use bevy::prelude::*;
-use bevy::ui::RelativeCursorPosition;
+use bevy::ui::{FocusPolicy, RelativeCursorPosition};
+use bevy::window::PrimaryWindow;
 
 use super::components::*;
+use super::scroll_container::{ScrollContainerElement, ScrollContainerWidget};
+use super::scroll_content::resolve_scroll_window;
+use super::scroll_minimap::ScrollMinimapElement;
+use super::scroll_skin::ScrollBarSkinElement;
+use super::scroll_theme::{scroll_theme, ScrollTheme};
 use super::styles::scroll_bar_style;
 use super::styles::scroll_handle_style;
-use super::ScrollSystemSet;
+use super::{run_ui_propagate_schedule, PropagateSchedule, ScrollSystemSet};
 
-use std::marker::PhantomData;
+use std::{collections::HashMap, marker::PhantomData};
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub struct ScrollBarWidget {
     pub orientation: ScrollBarOrientation,
+    pub placement: ScrollBarPlacement,
+    pub visibility: ScrollBarVisibility,
+    // Keeps an `Auto` bar's space reserved while it's hidden, so content doesn't reflow.
+    pub stable_gutter: bool,
     pub girth: Val,
     pub handle_girth: Val,
     pub handle_length: Val,
+    pub handle_sizing: ScrollHandleSizing,
+    pub min_handle_length: Val,
+    pub max_handle_length: Val,
     pub handle_visibility: bool,
+    // Derived from the container's content offset, see `update_scroll_bar_view`.
+    pub handle_size: f32,
     pub current_offset: f32,
 }
 
+impl ScrollBarWidget {
+    pub fn is_shown(&self) -> bool {
+        match self.visibility {
+            ScrollBarVisibility::Always => true,
+            ScrollBarVisibility::Auto => self.handle_visibility,
+            ScrollBarVisibility::Never => false,
+        }
+    }
+
+    // Whether the bar takes up space in the frame, shown or not.
+    pub fn occupies_space(&self) -> bool {
+        self.is_shown() || (self.stable_gutter && self.visibility == ScrollBarVisibility::Auto)
+    }
+
+    // Resolves the handle length in logical pixels for a bar `bar_length` long, given the
+    // visible share of the content.
+    pub fn resolve_handle_size(&self, bar_length: f32, visible_ratio: f32, ui_scale: f32) -> f32 {
+        let resolve = |val: Val| match val {
+            Val::Px(px) => Some(px * ui_scale),
+            Val::Percent(percent) => Some(bar_length * percent / 100.0),
+            Val::Auto | Val::Undefined => None,
+        };
+
+        let max = resolve(self.max_handle_length)
+            .unwrap_or(bar_length)
+            .min(bar_length)
+            .max(0.0);
+        let min = resolve(self.min_handle_length).unwrap_or(0.0).clamp(0.0, max);
+
+        match self.handle_sizing {
+            ScrollHandleSizing::Proportional => (visible_ratio * bar_length).clamp(min, max),
+            ScrollHandleSizing::Fixed => resolve(self.handle_length)
+                .unwrap_or(min)
+                .clamp(0.0, bar_length.max(0.0)),
+        }
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct ScrollBarElement {
     pub scroll_bar: Entity,
     pub scroll_handle: Entity,
 }
 
+// Interaction state of a bar's nodes, which picks their colours and handle girth from the theme.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect, FromReflect)]
+#[reflect(Component)]
+pub struct ScrollBarState {
+    pub track: ScrollPartState,
+    pub handle: ScrollPartState,
+}
+
+// Bar dimensions used when a container spawns its bars.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollBarSettings {
+    pub vertical_placement: ScrollBarPlacement,
+    pub horizontal_placement: ScrollBarPlacement,
+    pub vertical_visibility: ScrollBarVisibility,
+    pub horizontal_visibility: ScrollBarVisibility,
+    pub stable_gutter: bool,
+    pub girth: Val,
+    pub handle_girth: Val,
+    pub handle_length: Val,
+    pub handle_sizing: ScrollHandleSizing,
+    pub min_handle_length: Val,
+    pub max_handle_length: Val,
+}
+
+impl Default for ScrollBarSettings {
+    fn default() -> Self {
+        ScrollBarSettings {
+            vertical_placement: ScrollBarPlacement::default(),
+            horizontal_placement: ScrollBarPlacement::default(),
+            vertical_visibility: ScrollBarVisibility::Auto,
+            horizontal_visibility: ScrollBarVisibility::Auto,
+            stable_gutter: false,
+            girth: Val::Px(20.0),
+            handle_girth: Val::Px(18.0),
+            handle_length: Val::Px(40.0),
+            handle_sizing: ScrollHandleSizing::Proportional,
+            min_handle_length: Val::Px(20.0),
+            max_handle_length: Val::Percent(100.0),
+        }
+    }
+}
+
+impl ScrollBarSettings {
+    pub fn bar_widget(&self, orientation: ScrollBarOrientation) -> ScrollBarWidget {
+        ScrollBarWidget {
+            orientation,
+            placement: match orientation {
+                ScrollBarOrientation::Vertical => self.vertical_placement,
+                ScrollBarOrientation::Horizontal => self.horizontal_placement,
+            },
+            visibility: match orientation {
+                ScrollBarOrientation::Vertical => self.vertical_visibility,
+                ScrollBarOrientation::Horizontal => self.horizontal_visibility,
+            },
+            stable_gutter: self.stable_gutter,
+            girth: self.girth,
+            handle_girth: self.handle_girth,
+            handle_length: self.handle_length,
+            handle_sizing: self.handle_sizing,
+            min_handle_length: self.min_handle_length,
+            max_handle_length: self.max_handle_length,
+            handle_visibility: true,
+            handle_size: 0.0,
+            current_offset: 0.0,
+        }
+    }
+}
+
 pub struct ScrollBarPlugin;
 
 impl Plugin for ScrollBarPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<ScrollBarWidget>()
+            .register_type::<ScrollBarSettings>()
+            .register_type::<ScrollBarState>()
             .add_event::<UiEvent<ScrollBarWidget, PressInput>>()
             .add_system(init_scroll_bar.in_set(ScrollSystemSet::Create))
             .add_system(scroll_bar_left_click_publisher.in_set(ScrollSystemSet::Interact))
+            .add_system(scroll_bar_state_tracker.in_set(ScrollSystemSet::Interact))
             .add_system(scroll_bar_left_click_subscriber.in_set(ScrollSystemSet::Update))
-            .add_system(constrain_scroll_bar.in_set(ScrollSystemSet::Constrain))
-            .add_system(extract_scroll_bar.in_set(ScrollSystemSet::Extract));
+            .add_system(extract_scroll_bar.in_set(ScrollSystemSet::Extract))
+            .add_system(scroll_handle_cursor_icon.in_set(ScrollSystemSet::Extract))
+            .add_system(
+                extract_scroll_bar
+                    .in_set(ScrollSystemSet::Extract)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(
+                offset_scroll_handle
+                    .in_set(ScrollSystemSet::PostLayout)
+                    .after(run_ui_propagate_schedule),
+            );
     }
 }
 
 pub fn init_scroll_bar(
     q_uninitialized_widgets: Query<(Entity, &ScrollBarWidget), Without<ScrollBarElement>>,
+    q_themes: Query<&ScrollTheme>,
+    global_theme: Res<ScrollTheme>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_uninitialized_containers: Query<(), (With<ScrollContainerWidget>, Without<ScrollContainerElement>)>,
+    q_parents: Query<&Parent>,
     mut commands: Commands,
 ) {
     for (widget_entity, widget) in q_uninitialized_widgets.iter() {
+        // A bar loaded from a scene along with its container has no frame to go in until the
+        // container is initialized.
+        if q_parents
+            .get(widget_entity)
+            .map_or(false, |parent| q_uninitialized_containers.contains(parent.get()))
+        {
+            continue;
+        }
+
+        let theme = scroll_theme(widget_entity, &q_themes, &q_parents, &global_theme);
+
         let scroll_handle = commands
             .spawn(NodeBundle {
                 style: scroll_handle_style(),
-                background_color: BackgroundColor(Color::rgb(1.0, 1.0, 0.0)),
+                background_color: BackgroundColor(theme.handle.idle),
+                // The track underneath still has to see presses on the handle.
+                focus_policy: FocusPolicy::Pass,
                 ..default()
             })
+            .insert(Interaction::None)
             .insert(ControlledByElement {
                 element: widget_entity,
                 kind: PhantomData::<ScrollBarElement>,
@@ -57,11 +216,16 @@ pub fn init_scroll_bar(
 
         let scroll_bar = commands
             .spawn(NodeBundle {
-                style: scroll_bar_style(widget.orientation, widget.girth),
-                background_color: BackgroundColor(Color::rgb(1.0, 1.0, 0.0)),
+                style: scroll_bar_style(
+                    widget.orientation,
+                    widget.placement,
+                    widget.girth,
+                    UiRect::default(),
+                ),
+                background_color: BackgroundColor(theme.track.idle),
+                z_index: ZIndex::Local(1),
                 ..default()
             })
-            .insert(BackgroundColor(Color::rgb(0.15, 0.15, 0.15)))
             .insert(Interaction::None)
             .insert(RelativeCursorPosition::default())
             .insert(ControlledByElement {
@@ -72,10 +236,17 @@ pub fn init_scroll_bar(
 
         commands.entity(scroll_bar).add_child(scroll_handle);
 
-        commands.entity(widget_entity).insert(ScrollBarElement {
-            scroll_bar,
-            scroll_handle,
-        });
+        if let Some(container) = widget_or_parent(widget_entity, &q_container_elements, &q_parents) {
+            commands.entity(container.scroll_frame).add_child(scroll_bar);
+        }
+
+        commands.entity(widget_entity).insert((
+            ScrollBarElement {
+                scroll_bar,
+                scroll_handle,
+            },
+            ScrollBarState::default(),
+        ));
     }
 }
 
@@ -117,96 +288,327 @@ pub fn scroll_bar_left_click_publisher(
     }
 }
 
+// A press centres the handle on the cursor. The bar only requests the scroll; its handle follows
+// once the container's content has moved.
 pub fn scroll_bar_left_click_subscriber(
     mut er_scroll_bar_event: EventReader<UiEvent<ScrollBarWidget, PressInput>>,
-    mut q_scroll_bar_widgets: Query<&mut ScrollBarWidget, With<ScrollBarElement>>,
+    q_scroll_bar_widgets: Query<(&ScrollBarWidget, &ScrollBarElement, &Parent)>,
+    q_nodes: Query<&Node>,
+    mut ew_scroll_axis_to: EventWriter<UiEvent<ScrollContainerWidget, ScrollAxisTo>>,
 ) {
     for event in er_scroll_bar_event.iter() {
-        let mut widget = q_scroll_bar_widgets
-            .get_mut(event.widget)
+        let (widget, element, container) = q_scroll_bar_widgets
+            .get(event.widget)
             .expect("ScrollBarWidget event should have contained a ScrollBarWidget.");
 
-        let handle_length = match widget.handle_length {
-            Val::Undefined => 0.0,
-            Val::Auto => 0.0,
-            Val::Px(num) => num,
-            Val::Percent(num) => num,
-        };
+        let scroll_bar_node = q_nodes
+            .get(element.scroll_bar)
+            .expect("ScrollBarElement.scroll_bar should have contained a Node.");
 
-        let new_offset = match widget.orientation {
-            ScrollBarOrientation::Vertical => event.action.position.y - (handle_length / 2.0),
-            ScrollBarOrientation::Horizontal => event.action.position.x - (handle_length / 2.0),
+        let (position, bar_length) = match widget.orientation {
+            ScrollBarOrientation::Vertical => (event.action.position.y, scroll_bar_node.size().y),
+            ScrollBarOrientation::Horizontal => (event.action.position.x, scroll_bar_node.size().x),
         };
 
-        if widget.current_offset != new_offset {
-            widget.current_offset = new_offset;
+        let track_space = bar_length - widget.handle_size;
+        let handle_start = position - widget.handle_size / 2.0;
+
+        if track_space <= 0.0 {
+            continue;
         }
+
+        ew_scroll_axis_to.send(UiEvent {
+            widget: container.get(),
+            action: ScrollAxisTo {
+                axis: widget.orientation.axis(),
+                normalized: (handle_start / track_space).clamp(0.0, 1.0),
+                source: ScrollSource::Drag,
+            },
+            element_kind: PhantomData::<ScrollContainerWidget>,
+        });
     }
 }
 
-pub fn constrain_scroll_bar(
-    mut q_scroll_bar_widget: Query<
-        (&mut ScrollBarWidget, &ScrollBarElement),
-        Changed<ScrollBarWidget>,
-    >,
-    q_nodes: Query<&Node>,
+// Pressing anywhere on the bar drags the handle, so the handle counts as pressed while the
+// track is.
+pub fn scroll_bar_state_tracker(
+    mut q_scroll_bar_widgets: Query<(&ScrollBarWidget, &ScrollBarElement, &mut ScrollBarState)>,
+    q_interactions: Query<&Interaction>,
 ) {
-    for (mut widget, element) in q_scroll_bar_widget.iter_mut() {
-        let scroll_bar_node = q_nodes
-            .get(element.scroll_bar)
-            .expect("ScrollBarElement.scroll_bar should have contained a Node.");
-        let scroll_handle_node = q_nodes
-            .get(element.scroll_handle)
-            .expect("ScrollBarElement.scroll_handle should have contained a Node.");
+    for (widget, element, mut state) in q_scroll_bar_widgets.iter_mut() {
+        let interaction_state = |node: Entity| match q_interactions.get(node) {
+            Ok(Interaction::Clicked) => ScrollPartState::Pressed,
+            Ok(Interaction::Hovered) => ScrollPartState::Hovered,
+            _ => ScrollPartState::Idle,
+        };
 
-        let scroll_clamp = scroll_bar_node.size() - scroll_handle_node.size();
+        let new_state = if !widget.handle_visibility {
+            ScrollBarState {
+                track: ScrollPartState::Disabled,
+                handle: ScrollPartState::Disabled,
+            }
+        } else {
+            let track = interaction_state(element.scroll_bar);
+            let handle = match track {
+                ScrollPartState::Pressed => ScrollPartState::Pressed,
+                _ => interaction_state(element.scroll_handle),
+            };
 
-        let clamped_offset = match widget.orientation {
-            ScrollBarOrientation::Vertical => scroll_clamp.y.min(widget.current_offset).max(0.0),
-            ScrollBarOrientation::Horizontal => scroll_clamp.x.min(widget.current_offset).max(0.0),
+            ScrollBarState { track, handle }
         };
 
-        if widget.current_offset != clamped_offset {
-            widget.current_offset = clamped_offset;
+        if *state != new_state {
+            *state = new_state;
         }
     }
 }
 
 pub fn extract_scroll_bar(
-    q_scroll_bar_elements: Query<
-        (&ScrollBarElement, &ScrollBarWidget),
-        Or<(Changed<ScrollBarWidget>, Added<ScrollBarElement>)>,
-    >,
-    mut q_nodes: Query<&mut Style>,
+    q_scroll_bar_elements: Query<(
+        Entity,
+        &ScrollBarElement,
+        &ScrollBarWidget,
+        &ScrollBarState,
+        Option<&ScrollBarSkinElement>,
+        Option<&ScrollMinimapElement>,
+    )>,
+    mut q_nodes: Query<(&mut Style, &mut BackgroundColor)>,
+    q_themes: Query<&ScrollTheme>,
+    q_parents: Query<&Parent>,
+    global_theme: Res<ScrollTheme>,
+    ui_scale: Res<UiScale>,
 ) {
-    for (elem, widget) in q_scroll_bar_elements.iter() {
-        let mut scroll_handle_style = q_nodes
-            .get_mut(elem.scroll_handle)
-            .expect("ScrollBarElement.scroll_handle should have been a (Style, Node).");
+    let ui_scale = ui_scale.scale as f32;
 
-        scroll_handle_style.size = match widget.orientation {
-            ScrollBarOrientation::Vertical => Size {
-                width: widget.handle_girth,
-                height: widget.handle_length,
-            },
-            ScrollBarOrientation::Horizontal => Size {
-                height: widget.handle_girth,
-                width: widget.handle_length,
-            },
-        };
+    for (widget_entity, elem, widget, state, skin_elem, minimap_elem) in
+        q_scroll_bar_elements.iter()
+    {
+        let theme = scroll_theme(widget_entity, &q_themes, &q_parents, &global_theme);
 
-        scroll_handle_style.display = match widget.handle_visibility {
-            true => Display::DEFAULT,
-            false => Display::None,
+        let handle_length = Val::Px(widget.handle_size / ui_scale);
+        // A minimap's handle spans the whole girth to frame the blocks under it.
+        let handle_girth = match minimap_elem {
+            Some(_) => Val::Percent(100.0),
+            None => theme.handle_girth.map_or(widget.handle_girth, |girths| {
+                Val::Px(girths.get(state.handle) / ui_scale)
+            }),
         };
 
+        let mut new_handle_style = scroll_handle_style();
         match widget.orientation {
             ScrollBarOrientation::Vertical => {
-                scroll_handle_style.margin.top = Val::Px(widget.current_offset);
+                new_handle_style.size = Size::new(handle_girth, handle_length);
             }
             ScrollBarOrientation::Horizontal => {
-                scroll_handle_style.margin.left = Val::Px(widget.current_offset);
+                new_handle_style.size = Size::new(handle_length, handle_girth);
             }
         }
+        new_handle_style.display = match widget.handle_visibility {
+            true => Display::DEFAULT,
+            false => Display::None,
+        };
+
+        let (mut scroll_handle_style, mut scroll_handle_color) = q_nodes
+            .get_mut(elem.scroll_handle)
+            .expect("ScrollBarElement.scroll_handle should have been a (Style, Node).");
+
+        if *scroll_handle_style != new_handle_style {
+            *scroll_handle_style = new_handle_style;
+        }
+
+        // Skinned parts only show their images.
+        let handle_slices = skin_elem.and_then(|skin_elem| skin_elem.handle_slices);
+        let handle_color = match (handle_slices, minimap_elem) {
+            (Some(_), _) => Color::NONE,
+            (None, Some(minimap_elem)) => minimap_elem.minimap.viewport_color.get(state.handle),
+            (None, None) => theme.handle.get(state.handle),
+        };
+        if scroll_handle_color.0 != handle_color {
+            scroll_handle_color.0 = handle_color;
+        }
+
+        let (_, mut scroll_bar_color) = q_nodes
+            .get_mut(elem.scroll_bar)
+            .expect("ScrollBarElement.scroll_bar should have been a (Style, Node).");
+
+        let track_color = match skin_elem.and_then(|skin_elem| skin_elem.track_slices) {
+            Some(_) => Color::NONE,
+            None => theme.track.get(state.track),
+        };
+        if scroll_bar_color.0 != track_color {
+            scroll_bar_color.0 = track_color;
+        }
+    }
+}
+
+// The handle is laid out at the start of its bar and moved along it after layout, the same
+// way the content is, so scrolling or dragging never changes the bar's Style.
+pub fn offset_scroll_handle(
+    q_scroll_bar_elements: Query<(&ScrollBarElement, &ScrollBarWidget)>,
+    mut q_transforms: Query<&mut Transform>,
+) {
+    for (elem, widget) in q_scroll_bar_elements.iter() {
+        let Ok(mut scroll_handle_transform) = q_transforms.get_mut(elem.scroll_handle) else {
+            continue;
+        };
+
+        let handle_offset = match widget.orientation {
+            ScrollBarOrientation::Vertical => Vec2::new(0.0, widget.current_offset),
+            ScrollBarOrientation::Horizontal => Vec2::new(widget.current_offset, 0.0),
+        };
+
+        if handle_offset != Vec2::ZERO {
+            scroll_handle_transform.translation += handle_offset.extend(0.0);
+        }
+    }
+}
+
+// The icon a hovered or pressed handle put on a window, and the one it replaced.
+#[derive(Clone, Copy, Debug)]
+pub struct HandleCursor {
+    previous: CursorIcon,
+    icon: CursorIcon,
+}
+
+pub fn scroll_handle_cursor_icon(
+    q_scroll_bar_widgets: Query<(Entity, &ScrollBarState, &Parent)>,
+    q_container_widgets: Query<&ScrollContainerWidget>,
+    q_themes: Query<&ScrollTheme>,
+    q_parents: Query<&Parent>,
+    global_theme: Res<ScrollTheme>,
+    q_cameras: Query<&Camera>,
+    q_primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut q_windows: Query<&mut Window>,
+    mut cursor_windows: Local<HashMap<Entity, HandleCursor>>,
+) {
+    let mut active_windows = HashMap::new();
+
+    for (widget_entity, state, container) in q_scroll_bar_widgets.iter() {
+        if !matches!(state.handle, ScrollPartState::Hovered | ScrollPartState::Pressed) {
+            continue;
+        }
+
+        let theme = scroll_theme(widget_entity, &q_themes, &q_parents, &global_theme);
+        let Some(icon) = theme.handle_cursor else {
+            continue;
+        };
+
+        let camera = q_container_widgets
+            .get(container.get())
+            .ok()
+            .and_then(|container_widget| container_widget.camera);
+        let Some(window_entity) =
+            resolve_scroll_window(camera, &q_cameras, q_primary_window.get_single().ok())
+        else {
+            continue;
+        };
+
+        if let Ok(mut window) = q_windows.get_mut(window_entity) {
+            let previous = active_windows
+                .get(&window_entity)
+                .or_else(|| cursor_windows.get(&window_entity))
+                .map_or(window.cursor.icon, |cursor: &HandleCursor| cursor.previous);
+
+            if window.cursor.icon != icon {
+                window.cursor.icon = icon;
+            }
+            active_windows.insert(window_entity, HandleCursor { previous, icon });
+        }
+    }
+
+    // Windows no handle wants any more get their icon back, unless something else has changed
+    // it since.
+    for (window_entity, cursor) in cursor_windows.iter() {
+        if active_windows.contains_key(window_entity) {
+            continue;
+        }
+
+        if let Ok(mut window) = q_windows.get_mut(*window_entity) {
+            if window.cursor.icon == cursor.icon {
+                window.cursor.icon = cursor.previous;
+            }
+        }
+    }
+
+    *cursor_windows = active_windows;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(handle_sizing: ScrollHandleSizing, min: Val, max: Val, length: Val) -> ScrollBarWidget {
+        ScrollBarWidget {
+            handle_sizing,
+            min_handle_length: min,
+            max_handle_length: max,
+            handle_length: length,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn px_lengths_scale_with_ui_and_percent_lengths_with_the_bar() {
+        let px = bar(ScrollHandleSizing::Proportional, Val::Auto, Val::Px(50.0), Val::Auto);
+        assert_eq!(px.resolve_handle_size(400.0, 1.0, 2.0), 100.0);
+
+        let percent = bar(
+            ScrollHandleSizing::Proportional,
+            Val::Auto,
+            Val::Percent(50.0),
+            Val::Auto,
+        );
+        assert_eq!(percent.resolve_handle_size(400.0, 1.0, 2.0), 200.0);
+    }
+
+    #[test]
+    fn proportional_handle_follows_the_visible_share() {
+        let widget = bar(ScrollHandleSizing::Proportional, Val::Auto, Val::Auto, Val::Auto);
+        assert_eq!(widget.resolve_handle_size(400.0, 0.25, 1.0), 100.0);
+        assert_eq!(widget.resolve_handle_size(400.0, 2.0, 1.0), 400.0);
+    }
+
+    #[test]
+    fn min_length_wins_over_a_smaller_visible_share() {
+        let widget = bar(ScrollHandleSizing::Proportional, Val::Px(40.0), Val::Auto, Val::Auto);
+        assert_eq!(widget.resolve_handle_size(400.0, 0.05, 1.0), 40.0);
+    }
+
+    #[test]
+    fn max_length_below_min_length_caps_it() {
+        let widget = bar(
+            ScrollHandleSizing::Proportional,
+            Val::Px(100.0),
+            Val::Px(50.0),
+            Val::Auto,
+        );
+        assert_eq!(widget.resolve_handle_size(400.0, 0.05, 1.0), 50.0);
+        assert_eq!(widget.resolve_handle_size(400.0, 0.9, 1.0), 50.0);
+    }
+
+    #[test]
+    fn fixed_handle_uses_its_length_or_falls_back_to_min() {
+        let fixed = bar(ScrollHandleSizing::Fixed, Val::Px(20.0), Val::Auto, Val::Px(30.0));
+        assert_eq!(fixed.resolve_handle_size(400.0, 0.9, 1.0), 30.0);
+
+        let auto = bar(ScrollHandleSizing::Fixed, Val::Px(20.0), Val::Auto, Val::Auto);
+        assert_eq!(auto.resolve_handle_size(400.0, 0.9, 1.0), 20.0);
+
+        let unset = bar(ScrollHandleSizing::Fixed, Val::Auto, Val::Auto, Val::Auto);
+        assert_eq!(unset.resolve_handle_size(400.0, 0.9, 1.0), 0.0);
+    }
+
+    #[test]
+    fn zero_length_bar_has_an_empty_handle() {
+        let proportional = bar(
+            ScrollHandleSizing::Proportional,
+            Val::Px(20.0),
+            Val::Auto,
+            Val::Auto,
+        );
+        assert_eq!(proportional.resolve_handle_size(0.0, 0.5, 1.0), 0.0);
+
+        let fixed = bar(ScrollHandleSizing::Fixed, Val::Px(20.0), Val::Auto, Val::Px(30.0));
+        assert_eq!(fixed.resolve_handle_size(0.0, 0.5, 1.0), 0.0);
     }
 }
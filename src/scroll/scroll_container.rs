@@ -1,77 +1,276 @@
-use bevy::prelude::*;
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    ui::flex_node_system,
+};
 
 use super::{
-    components::{ScrollBarOrientation, ScrollDirection},
-    scroll_bar::{ScrollBarElement, ScrollBarWidget},
-    scroll_content::{ScrollContentElement, ScrollContentWidget},
-    PropagateSchedule, ScrollSystemSet,
+    components::*,
+    scroll_bar::{
+        scroll_bar_left_click_subscriber, ScrollBarElement, ScrollBarSettings, ScrollBarWidget,
+    },
+    scroll_content::{ScrollContentElement, ScrollContentWidget, ScrollEventState},
+    scroll_minimap::ScrollMinimapElement,
+    scroll_theme::ScrollTheme,
+    styles::{scroll_bar_style, scroll_corner_style, scroll_frame_style},
+    PropagateSchedule, ScrollLayoutPass, ScrollSystemSet,
 };
 
+// Size of a container, bars included, applied when it is initialized.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollViewport {
+    pub width: Val,
+    pub height: Val,
+}
+
+impl Default for ScrollViewport {
+    fn default() -> Self {
+        ScrollViewport {
+            width: Val::Percent(30.0),
+            height: Val::Percent(30.0),
+        }
+    }
+}
+
+// Mounts a container's frame under another UI node; without it the frame is a root node. Put
+// it inside an outer container's content to nest containers, so wheel input the inner one
+// can't use chains out to the outer one. Make the inner container a child of the outer one too,
+// or it outlives the nodes its frame was mounted in.
+#[derive(Component, Clone, Copy, Debug, Reflect, FromReflect)]
+#[reflect(Component, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollFrameParent(pub Entity);
+
+// Only used by reflection.
+impl FromWorld for ScrollFrameParent {
+    fn from_world(_world: &mut World) -> Self {
+        ScrollFrameParent(Entity::PLACEHOLDER)
+    }
+}
+
+// Like `Parent`, a node outside the loaded scene keeps its entity.
+impl MapEntities for ScrollFrameParent {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        if let Ok(mapped_entity) = entity_map.get(self.0) {
+            self.0 = mapped_entity;
+        }
+        Ok(())
+    }
+}
+
 pub struct ScrollContainerPlugin;
 
 impl Plugin for ScrollContainerPlugin {
     fn build(&self, app: &mut App) {
         app
-            //.add_system(synchronize_bar_with_content.in_schedule(PropagateSchedule))
-            .add_system(synchronize_bar_with_content.in_set(ScrollSystemSet::Propagate))
-            .add_system(init_scroll_container.in_set(ScrollSystemSet::Create));
+            .register_type::<ScrollContainerWidget>()
+            .register_type::<ScrollMetrics>()
+            .register_type::<ScrollViewport>()
+            .register_type::<ScrollFrameParent>()
+            .add_event::<UiEvent<ScrollContainerWidget, Scrolled>>()
+            .add_event::<UiEvent<ScrollContainerWidget, ReachedEdge>>()
+            .add_event::<UiEvent<ScrollContainerWidget, OverflowChanged>>()
+            .add_event::<UiEvent<ScrollContainerWidget, ScrollTo>>()
+            .add_event::<UiEvent<ScrollContainerWidget, ScrollAxisTo>>()
+            .add_system(scroll_container_scroll_to_subscriber.in_set(ScrollSystemSet::Update))
+            .add_system(
+                scroll_container_scroll_axis_to_subscriber
+                    .in_set(ScrollSystemSet::Update)
+                    .after(scroll_bar_left_click_subscriber),
+            )
+            .add_system(update_scroll_bar_view.in_set(ScrollSystemSet::Propagate))
+            .add_system(layout_scroll_container.in_set(ScrollSystemSet::Extract))
+            .add_system(
+                layout_scroll_container
+                    .in_set(ScrollSystemSet::Extract)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(update_scroll_metrics.in_set(ScrollSystemSet::Extract))
+            .add_system(
+                scroll_container_event_publisher
+                    .in_set(ScrollSystemSet::Extract)
+                    .after(update_scroll_metrics),
+            )
+            .add_system(init_scroll_container.in_set(ScrollSystemSet::Create))
+            .add_system(delete_scroll_container.in_set(ScrollSystemSet::Extract))
+            .add_system(
+                mark_resized_scroll_containers
+                    .in_set(ScrollSystemSet::Update)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(
+                update_scroll_bar_view
+                    .in_set(ScrollSystemSet::Propagate)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(
+                update_scroll_metrics
+                    .after(flex_node_system)
+                    .in_schedule(PropagateSchedule),
+            );
     }
 }
 
-#[derive(Component, Clone, Debug)]
+// Offsets within this many pixels of the end count as having reached it, since the bar to
+// content ratio rarely lands exactly on the threshold.
+const EDGE_TOLERANCE: f32 = 0.5;
+
+#[derive(Component, Clone, Debug, Default, Reflect, FromReflect)]
+#[reflect(Component, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub struct ScrollContainerWidget {
     pub scroll_direction: ScrollDirection,
+    pub overscroll_behavior: OverscrollBehavior,
+    pub camera: Option<Entity>,
+}
+
+impl MapEntities for ScrollContainerWidget {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        if let Some(camera) = self.camera.as_mut() {
+            if let Ok(mapped_camera) = entity_map.get(*camera) {
+                *camera = mapped_camera;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Component, Clone, Debug)]
 pub struct ScrollContainerElement {
+    pub scroll_frame: Entity,
+    pub scroll_corner: Option<Entity>,
     pub scroll_bar_widgets: Vec<Entity>,
     pub scroll_content_widget: Entity,
 }
 
+// Read-only view of a container's scroll state, kept up to date every frame.
+// Send `UiEvent<ScrollContainerWidget, ScrollTo>` to move it.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect, FromReflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollMetrics {
+    pub viewport_size: Vec2,
+    pub content_size: Vec2,
+    pub max_offset: Vec2,
+    pub offset: Vec2,
+    // `offset / max_offset` per axis, or zero on axes that can't scroll.
+    pub normalized: Vec2,
+    pub can_scroll: BVec2,
+}
+
+impl ScrollMetrics {
+    pub fn offset_from_normalized(&self, normalized: Vec2) -> Vec2 {
+        normalized.clamp(Vec2::ZERO, Vec2::ONE) * self.max_offset
+    }
+}
+
 pub fn init_scroll_container(
     q_uninitialized_widgets: Query<
-        (Entity, &ScrollContainerWidget),
+        (
+            Entity,
+            &ScrollContainerWidget,
+            Option<&ScrollBarSettings>,
+            Option<&ScrollViewport>,
+            Option<&ScrollTheme>,
+            Option<&ScrollFrameParent>,
+            Option<&Children>,
+        ),
         Without<ScrollContainerElement>,
     >,
+    q_bar_widgets: Query<&ScrollBarWidget>,
+    q_content_widgets: Query<(), With<ScrollContentWidget>>,
+    global_theme: Res<ScrollTheme>,
     mut commands: Commands,
 ) {
-    for (entity, widget) in q_uninitialized_widgets.iter() {
-        let v_scroll_bar_widget = commands
-            .spawn(ScrollBarWidget {
-                orientation: ScrollBarOrientation::Vertical,
-                girth: Val::Px(20.0),
-                handle_girth: Val::Px(18.0),
-                handle_length: Val::Px(40.0),
-                handle_visibility: true,
-                current_offset: 0.0,
-            })
-            .id();
+    for (entity, widget, bar_settings, viewport, theme, frame_parent, children) in
+        q_uninitialized_widgets.iter()
+    {
+        // Elements aren't saved in scenes, so a loaded container is rebuilt here and keeps the
+        // bar and content widgets it was saved with.
+        let children = children.map_or(&[][..], |children| &children[..]);
+
+        let bar_settings = bar_settings.cloned().unwrap_or_default();
+        let viewport = viewport.cloned().unwrap_or_default();
+        let theme = theme.unwrap_or(&global_theme);
 
-        let h_scroll_bar_widget = commands
-            .spawn(ScrollBarWidget {
-                orientation: ScrollBarOrientation::Horizontal,
-                girth: Val::Px(20.0),
-                handle_girth: Val::Px(18.0),
-                handle_length: Val::Px(40.0),
-                handle_visibility: true,
-                current_offset: 0.0,
+        let scroll_frame = commands
+            .spawn(NodeBundle {
+                style: scroll_frame_style(Size::new(viewport.width, viewport.height)),
+                ..default()
+            })
+            .insert(ControlledByElement {
+                element: entity,
+                kind: PhantomData::<ScrollContainerElement>,
             })
             .id();
 
-        let scroll_bar_widgets = match widget.scroll_direction {
-            ScrollDirection::Vertical => vec![v_scroll_bar_widget],
-            ScrollDirection::Horizontal => vec![h_scroll_bar_widget],
-            ScrollDirection::Both => vec![v_scroll_bar_widget, h_scroll_bar_widget],
-            ScrollDirection::Neither => todo!(),
+        if let Some(frame_parent) = frame_parent {
+            commands.entity(frame_parent.0).add_child(scroll_frame);
+        }
+
+        let scroll_corner = (widget.scroll_direction == ScrollDirection::Both).then(|| {
+            let scroll_corner = commands
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(theme.track.idle),
+                    z_index: ZIndex::Local(1),
+                    ..default()
+                })
+                .id();
+            commands.entity(scroll_frame).add_child(scroll_corner);
+            scroll_corner
+        });
+
+        let bar_orientations = match widget.scroll_direction {
+            ScrollDirection::Vertical => vec![ScrollBarOrientation::Vertical],
+            ScrollDirection::Horizontal => vec![ScrollBarOrientation::Horizontal],
+            ScrollDirection::Both => {
+                vec![ScrollBarOrientation::Vertical, ScrollBarOrientation::Horizontal]
+            }
+            ScrollDirection::Neither => Vec::new(),
         };
 
-        let scroll_content_widget = commands
-            .spawn(ScrollContentWidget {
-                scroll_direction: widget.scroll_direction,
-                current_offset: Vec2::ZERO,
+        // Only the bars the container uses are spawned; an unparented spare would never be
+        // despawned with it.
+        let scroll_bar_widgets: Vec<Entity> = bar_orientations
+            .into_iter()
+            .map(|orientation| {
+                children
+                    .iter()
+                    .copied()
+                    .find(|child| {
+                        q_bar_widgets
+                            .get(*child)
+                            .map_or(false, |bar_widget| bar_widget.orientation == orientation)
+                    })
+                    .unwrap_or_else(|| commands.spawn(bar_settings.bar_widget(orientation)).id())
             })
-            .id();
+            .collect();
+
+        let scroll_content_widget = children
+            .iter()
+            .copied()
+            .find(|child| q_content_widgets.contains(*child))
+            .unwrap_or_else(|| {
+                commands
+                    .spawn(ScrollContentWidget {
+                        scroll_direction: widget.scroll_direction,
+                        overscroll_behavior: widget.overscroll_behavior,
+                        camera: widget.camera,
+                        current_offset: Vec2::ZERO,
+                    })
+                    .id()
+            });
 
         for widget in &scroll_bar_widgets {
             commands.entity(entity).add_child(*widget);
@@ -80,147 +279,453 @@ pub fn init_scroll_container(
         commands
             .entity(entity)
             .add_child(scroll_content_widget)
-            .insert(ScrollContainerElement {
-                scroll_bar_widgets,
-                scroll_content_widget,
-            });
+            .insert((
+                ScrollContainerElement {
+                    scroll_frame,
+                    scroll_corner,
+                    scroll_bar_widgets,
+                    scroll_content_widget,
+                },
+                ScrollMetrics::default(),
+            ));
     }
 }
 
-pub fn synchronize_bar_with_content(
-    q_container_widget: Query<&ScrollContainerElement>,
-    mut pq_bar: ParamSet<(
-        Query<
-            (&ScrollBarWidget, &ScrollBarElement),
-            Or<(
-                Changed<ScrollBarWidget>,
-                Added<ScrollBarElement>,
-            )>,
-        >,
-        Query<(&mut ScrollBarWidget, &ScrollBarElement)>,
-    )>,
-    mut pq_content: ParamSet<(
-        Query<
-            (&ScrollContentWidget, &ScrollContentElement),
-            Or<(
-                Changed<ScrollContentWidget>,
-                Added<ScrollContentElement>,
-            )>,
-        >,
-        Query<(&mut ScrollContentWidget, &ScrollContentElement)>,
-    )>,
-    q_nodes: Query<&Node>,
+// The frame isn't a child of its container, so despawning the container leaves it behind.
+// Frames whose container is gone, or has been rebuilt with a new frame, are despawned along
+// with the bar, corner and wrapper nodes inside them.
+pub fn delete_scroll_container(
+    q_frames: Query<(Entity, &ControlledByElement<ScrollContainerElement>)>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    mut commands: Commands,
 ) {
-    // Apply scroll new bar offsets to content offsets
-    for elem in q_container_widget.iter() {
-        for scroll_bar_widget in elem.scroll_bar_widgets.iter() {
-            if let Ok((changed_bar_widget, changed_bar_element)) =
-                pq_bar.p0().get(*scroll_bar_widget)
-            {
-                if let Ok((mut stored_content_widget, stored_content_element)) =
-                    pq_content.p1().get_mut(elem.scroll_content_widget)
+    for (scroll_frame, controlled_by) in q_frames.iter() {
+        let orphaned = q_container_elements
+            .get(controlled_by.element)
+            .map_or(true, |elem| elem.scroll_frame != scroll_frame);
+
+        if orphaned {
+            commands.entity(scroll_frame).despawn_recursive();
+        }
+    }
+}
+
+// Places the wrapper, bars and corner within the frame. Outside bars push the wrapper in from
+// their side, and with two bars each one stops short of the other's girth so they meet at the
+// corner square instead of overlapping. Bars hidden by their visibility policy give their space
+// back unless they keep a stable gutter.
+pub fn layout_scroll_container(
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_bar_widgets: Query<(&ScrollBarWidget, &ScrollBarElement)>,
+    q_content_elements: Query<&ScrollContentElement>,
+    mut q_styles: Query<&mut Style>,
+) {
+    for elem in q_container_elements.iter() {
+        let bars: Vec<(&ScrollBarWidget, &ScrollBarElement)> = elem
+            .scroll_bar_widgets
+            .iter()
+            .filter_map(|bar_widget| q_bar_widgets.get(*bar_widget).ok())
+            .collect();
+
+        let bar = |orientation: ScrollBarOrientation| {
+            bars.iter()
+                .find(|(widget, _)| widget.orientation == orientation && widget.occupies_space())
+        };
+        let vertical_bar = bar(ScrollBarOrientation::Vertical);
+        let horizontal_bar = bar(ScrollBarOrientation::Horizontal);
+
+        let mut wrapper_insets = UiRect::all(Val::Px(0.0));
+
+        for (widget, _) in bars.iter() {
+            if widget.placement.overlap == ScrollBarOverlap::Inset || !widget.occupies_space() {
+                continue;
+            }
+
+            match (widget.orientation, widget.placement.side) {
+                (ScrollBarOrientation::Vertical, ScrollBarSide::Start) => wrapper_insets.left = widget.girth,
+                (ScrollBarOrientation::Vertical, ScrollBarSide::End) => wrapper_insets.right = widget.girth,
+                (ScrollBarOrientation::Horizontal, ScrollBarSide::Start) => wrapper_insets.top = widget.girth,
+                (ScrollBarOrientation::Horizontal, ScrollBarSide::End) => wrapper_insets.bottom = widget.girth,
+            }
+        }
+
+        for (widget, element) in bars.iter() {
+            let crossing_bar = match widget.orientation {
+                ScrollBarOrientation::Vertical => horizontal_bar,
+                ScrollBarOrientation::Horizontal => vertical_bar,
+            };
+
+            let mut bar_insets = UiRect::all(Val::Px(0.0));
+            if let Some((crossing_widget, _)) = crossing_bar {
+                match (widget.orientation, crossing_widget.placement.side) {
+                    (ScrollBarOrientation::Vertical, ScrollBarSide::Start) => bar_insets.top = crossing_widget.girth,
+                    (ScrollBarOrientation::Vertical, ScrollBarSide::End) => bar_insets.bottom = crossing_widget.girth,
+                    (ScrollBarOrientation::Horizontal, ScrollBarSide::Start) => bar_insets.left = crossing_widget.girth,
+                    (ScrollBarOrientation::Horizontal, ScrollBarSide::End) => bar_insets.right = crossing_widget.girth,
+                }
+            }
+
+            let mut new_style =
+                scroll_bar_style(widget.orientation, widget.placement, widget.girth, bar_insets);
+            if !widget.is_shown() {
+                new_style.display = Display::None;
+            }
+            if let Ok(mut bar_style) = q_styles.get_mut(element.scroll_bar) {
+                if *bar_style != new_style {
+                    *bar_style = new_style;
+                }
+            }
+        }
+
+        if let Some(scroll_corner) = elem.scroll_corner {
+            let new_style = match (vertical_bar, horizontal_bar) {
+                (Some((vertical, _)), Some((horizontal, _)))
+                    if vertical.is_shown() && horizontal.is_shown() =>
                 {
-                    let bar_node = q_nodes
-                        .get(changed_bar_element.scroll_bar)
-                        .expect("shouldve been a node");
-                    let content_node = q_nodes
-                        .get(stored_content_element.scroll_content)
-                        .expect("also shouldve been a node");
-                    let handle_node = q_nodes
-                        .get(changed_bar_element.scroll_handle)
-                        .expect("also should have been a node");
-                    let wrapper_node = q_nodes.get(stored_content_element.scroll_wrapper).unwrap();
-
-                    let content_scrollable_space = content_node.size() - wrapper_node.size();
-                    let bar_scrollable_space = bar_node.size() - handle_node.size();
-                    let ratio = bar_scrollable_space / content_scrollable_space;
-
-                    match changed_bar_widget.orientation {
-                        ScrollBarOrientation::Vertical => {
-                            let new_offset = changed_bar_widget.current_offset / ratio.y;
-                            if (new_offset - stored_content_widget.current_offset.y).abs()
-                                > f32::EPSILON
-                            {
-                                stored_content_widget.current_offset.y = new_offset;
-                            }
-                        }
-                        ScrollBarOrientation::Horizontal => {
-                            let new_offset = changed_bar_widget.current_offset / ratio.x;
-                            if (new_offset - stored_content_widget.current_offset.x).abs()
-                                > f32::EPSILON
-                            {
-                                stored_content_widget.current_offset.x = new_offset;
-                            }
-                        }
-                    };
+                    scroll_corner_style(
+                        (vertical.placement.side, vertical.girth),
+                        (horizontal.placement.side, horizontal.girth),
+                    )
                 }
+                _ => Style {
+                    display: Display::None,
+                    ..default()
+                },
             };
+
+            if let Ok(mut corner_style) = q_styles.get_mut(scroll_corner) {
+                if *corner_style != new_style {
+                    *corner_style = new_style;
+                }
+            }
         }
 
-        // Apply new scroll content offset to bar offsets
-        if let Ok((changed_content_widget, changed_content_element)) =
-            pq_content.p0().get(elem.scroll_content_widget)
+        let Ok(content_element) = q_content_elements.get(elem.scroll_content_widget) else {
+            continue;
+        };
+
+        if let Ok(mut wrapper_style) = q_styles.get_mut(content_element.scroll_wrapper) {
+            if wrapper_style.position != wrapper_insets {
+                wrapper_style.position = wrapper_insets;
+            }
+        }
+    }
+}
+
+// Layout changes don't touch the widgets, so mark the content of any container whose nodes were
+// resized as changed to have it re-constrained and re-synchronized with its bars.
+pub fn mark_resized_scroll_containers(
+    q_container_elements: Query<&ScrollContainerElement>,
+    mut q_content_widgets: Query<(&mut ScrollContentWidget, &ScrollContentElement)>,
+    q_bar_elements: Query<&ScrollBarElement>,
+    q_resized_nodes: Query<(), Changed<Node>>,
+    mut layout_pass: ResMut<ScrollLayoutPass>,
+) {
+    layout_pass.resized = false;
+
+    for elem in q_container_elements.iter() {
+        let (mut content_widget, content_element) =
+            if let Ok(content) = q_content_widgets.get_mut(elem.scroll_content_widget) {
+                content
+            } else {
+                continue;
+            };
+
+        let bars_resized = elem.scroll_bar_widgets.iter().any(|bar_widget| {
+            q_bar_elements.get(*bar_widget).map_or(false, |bar_element| {
+                q_resized_nodes.contains(bar_element.scroll_bar)
+                    || q_resized_nodes.contains(bar_element.scroll_handle)
+            })
+        });
+
+        if bars_resized
+            || q_resized_nodes.contains(content_element.scroll_content)
+            || q_resized_nodes.contains(content_element.scroll_wrapper)
         {
-            for scroll_bar_widget in elem.scroll_bar_widgets.iter() {
-                if let Ok((mut stored_bar_widget, stored_bar_element)) =
-                    pq_bar.p1().get_mut(*scroll_bar_widget)
-                {
-                    let bar_node = q_nodes
-                        .get(stored_bar_element.scroll_bar)
-                        .expect("shouldve been a node");
-                    let content_node = q_nodes
-                        .get(changed_content_element.scroll_content)
-                        .expect("also shouldve been a node");
-                    let handle_node = q_nodes
-                        .get(stored_bar_element.scroll_handle)
-                        .expect("also should have been a node");
-                    let wrapper_node = q_nodes.get(changed_content_element.scroll_wrapper).unwrap();
-
-                    let content_scrollable_space = content_node.size() - wrapper_node.size();
-                    let bar_scrollable_space = bar_node.size() - handle_node.size();
-                    let bar_to_content_ratio = bar_scrollable_space / content_scrollable_space;
-                    let wrapper_to_content_ratio = wrapper_node.size() / content_node.size();
-                    let new_handle_size = wrapper_to_content_ratio * bar_node.size();
-
-                    match stored_bar_widget.orientation {
-                        ScrollBarOrientation::Vertical => {
-                            let is_scrollable = content_scrollable_space.y > 0.0;
-
-                            if is_scrollable != stored_bar_widget.handle_visibility {
-                                stored_bar_widget.handle_visibility = is_scrollable;
-                            }
-
-                            if (new_handle_size.y - handle_node.size().y).abs() > f32::EPSILON {
-                                stored_bar_widget.handle_length = Val::Px(new_handle_size.y);
-                            }
-
-                            let new_offset = changed_content_widget.current_offset.y * bar_to_content_ratio.y;
-                            if (new_offset - stored_bar_widget.current_offset).abs() > f32::EPSILON
-                            {
-                                stored_bar_widget.current_offset = new_offset;
-                            }
-                        }
-                        ScrollBarOrientation::Horizontal => {
-                            let is_scrollable = content_scrollable_space.x > 0.0;
-
-                            if is_scrollable != stored_bar_widget.handle_visibility {
-                                stored_bar_widget.handle_visibility = is_scrollable;
-                            }
-
-                            
-                            if (new_handle_size.x - handle_node.size().x).abs() > f32::EPSILON {
-                                stored_bar_widget.handle_length = Val::Px(new_handle_size.x);
-                            }
-
-                            let new_offset = changed_content_widget.current_offset.x * bar_to_content_ratio.x;
-                            if (new_offset - stored_bar_widget.current_offset).abs() > f32::EPSILON
-                            {
-                                stored_bar_widget.current_offset = new_offset;
-                            }
-                        }
-                    }
-                }
+            content_widget.set_changed();
+            layout_pass.resized = true;
+        }
+    }
+}
+
+// The content offset is the only scroll position; bars just display it.
+pub fn update_scroll_bar_view(
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    mut q_bar_widgets: Query<(
+        &mut ScrollBarWidget,
+        &ScrollBarElement,
+        Option<&ScrollMinimapElement>,
+    )>,
+    q_nodes: Query<&Node>,
+    ui_scale: Res<UiScale>,
+) {
+    let ui_scale = ui_scale.scale as f32;
+
+    for elem in q_container_elements.iter() {
+        let (content_widget, content_element) =
+            if let Ok(content) = q_content_widgets.get(elem.scroll_content_widget) {
+                content
+            } else {
+                continue;
+            };
+
+        let (content_node, wrapper_node) = if let (Ok(content_node), Ok(wrapper_node)) = (
+            q_nodes.get(content_element.scroll_content),
+            q_nodes.get(content_element.scroll_wrapper),
+        ) {
+            (content_node, wrapper_node)
+        } else {
+            continue;
+        };
+
+        let max_offset = content_node.size() - wrapper_node.size();
+        let visible_ratio = wrapper_node.size() / content_node.size();
+
+        for scroll_bar_widget in elem.scroll_bar_widgets.iter() {
+            let (mut bar_widget, bar_element, minimap_elem) =
+                if let Ok(bar) = q_bar_widgets.get_mut(*scroll_bar_widget) {
+                    bar
+                } else {
+                    continue;
+                };
+
+            let bar_node = if let Ok(bar_node) = q_nodes.get(bar_element.scroll_bar) {
+                bar_node
+            } else {
+                continue;
+            };
+
+            let (max_offset, offset, bar_length, visible_ratio) = match bar_widget.orientation {
+                ScrollBarOrientation::Vertical => (
+                    max_offset.y,
+                    content_widget.current_offset.y,
+                    bar_node.size().y,
+                    visible_ratio.y,
+                ),
+                ScrollBarOrientation::Horizontal => (
+                    max_offset.x,
+                    content_widget.current_offset.x,
+                    bar_node.size().x,
+                    visible_ratio.x,
+                ),
+            };
+
+            // Offsets are mapped onto the space the resolved handle leaves free, so a clamped
+            // or fixed size handle still reaches both ends of the bar.
+            let is_scrollable = max_offset > 0.0;
+            // A minimap's handle covers exactly the part of the drawn content that's visible,
+            // whatever sizing the bar itself has.
+            let new_handle_size = match minimap_elem {
+                Some(_) => (visible_ratio * bar_length).clamp(0.0, bar_length.max(0.0)),
+                None => bar_widget.resolve_handle_size(bar_length, visible_ratio, ui_scale),
+            };
+            let new_offset = if is_scrollable {
+                offset / max_offset * (bar_length - new_handle_size).max(0.0)
+            } else {
+                0.0
+            };
+
+            if bar_widget.handle_visibility != is_scrollable {
+                bar_widget.handle_visibility = is_scrollable;
+            }
+
+            if is_scrollable && bar_widget.handle_size != new_handle_size {
+                bar_widget.handle_size = new_handle_size;
+            }
+
+            if bar_widget.current_offset != new_offset {
+                bar_widget.current_offset = new_offset;
+            }
+        }
+    }
+}
+
+pub fn scroll_container_scroll_axis_to_subscriber(
+    mut er_scroll_axis_to: EventReader<UiEvent<ScrollContainerWidget, ScrollAxisTo>>,
+    q_container_elements: Query<(&ScrollContainerElement, &ScrollMetrics)>,
+    mut q_content_widgets: Query<(&mut ScrollContentWidget, &mut ScrollEventState)>,
+) {
+    for event in er_scroll_axis_to.iter() {
+        let Ok((elem, metrics)) = q_container_elements.get(event.widget) else {
+            continue;
+        };
+
+        let Ok((mut widget, mut event_state)) =
+            q_content_widgets.get_mut(elem.scroll_content_widget)
+        else {
+            continue;
+        };
+
+        match event.action.axis {
+            ScrollAxis::Horizontal => {
+                widget.current_offset.x = event.action.normalized * metrics.max_offset.x;
+            }
+            ScrollAxis::Vertical => {
+                widget.current_offset.y = event.action.normalized * metrics.max_offset.y;
+            }
+        }
+        event_state.source = Some(event.action.source);
+    }
+}
+
+pub fn update_scroll_metrics(
+    mut q_container_elements: Query<(&ScrollContainerElement, &mut ScrollMetrics)>,
+    q_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    q_nodes: Query<&Node>,
+) {
+    for (elem, mut metrics) in q_container_elements.iter_mut() {
+        let (widget, element) =
+            if let Ok(content) = q_content_widgets.get(elem.scroll_content_widget) {
+                content
+            } else {
+                continue;
+            };
+
+        let (content_node, wrapper_node) = if let (Ok(content_node), Ok(wrapper_node)) = (
+            q_nodes.get(element.scroll_content),
+            q_nodes.get(element.scroll_wrapper),
+        ) {
+            (content_node, wrapper_node)
+        } else {
+            continue;
+        };
+
+        let max_offset = (content_node.size() - wrapper_node.size()).max(Vec2::ZERO);
+        let can_scroll = BVec2::new(
+            widget.scroll_direction.scrolls_x() && max_offset.x > 0.0,
+            widget.scroll_direction.scrolls_y() && max_offset.y > 0.0,
+        );
+        let normalized = Vec2 {
+            x: if can_scroll.x { widget.current_offset.x / max_offset.x } else { 0.0 },
+            y: if can_scroll.y { widget.current_offset.y / max_offset.y } else { 0.0 },
+        };
+
+        let new_metrics = ScrollMetrics {
+            viewport_size: wrapper_node.size(),
+            content_size: content_node.size(),
+            max_offset,
+            offset: widget.current_offset,
+            normalized,
+            can_scroll,
+        };
+
+        if *metrics != new_metrics {
+            *metrics = new_metrics;
+        }
+    }
+}
+
+pub fn scroll_container_scroll_to_subscriber(
+    mut er_scroll_to: EventReader<UiEvent<ScrollContainerWidget, ScrollTo>>,
+    q_container_elements: Query<(&ScrollContainerElement, &ScrollMetrics)>,
+    mut q_content_widgets: Query<(&mut ScrollContentWidget, &mut ScrollEventState)>,
+) {
+    for event in er_scroll_to.iter() {
+        // Containers that aren't built yet, or are already gone, ignore the event.
+        let Ok((elem, metrics)) = q_container_elements.get(event.widget) else {
+            continue;
+        };
+
+        let Ok((mut widget, mut event_state)) =
+            q_content_widgets.get_mut(elem.scroll_content_widget)
+        else {
+            continue;
+        };
+
+        widget.current_offset = match event.action {
+            ScrollTo::Offset(offset) => offset,
+            ScrollTo::Normalized(normalized) => metrics.offset_from_normalized(normalized),
+        };
+        event_state.source = Some(ScrollSource::Programmatic);
+    }
+}
+
+pub fn scroll_container_event_publisher(
+    q_container_elements: Query<(Entity, &ScrollContainerElement, &ScrollMetrics)>,
+    mut q_content_widgets: Query<&mut ScrollEventState>,
+    mut ew_scrolled: EventWriter<UiEvent<ScrollContainerWidget, Scrolled>>,
+    mut ew_reached_edge: EventWriter<UiEvent<ScrollContainerWidget, ReachedEdge>>,
+    mut ew_overflow_changed: EventWriter<UiEvent<ScrollContainerWidget, OverflowChanged>>,
+) {
+    for (container, elem, metrics) in q_container_elements.iter() {
+        let mut event_state =
+            if let Ok(event_state) = q_content_widgets.get_mut(elem.scroll_content_widget) {
+                event_state
+            } else {
+                continue;
+            };
+
+        let overflowing = metrics.can_scroll;
+        let scroll_threshold = metrics.max_offset;
+
+        if overflowing != event_state.overflowing {
+            event_state.overflowing = overflowing;
+            ew_overflow_changed.send(UiEvent {
+                widget: container,
+                action: OverflowChanged { overflowing },
+                element_kind: PhantomData::<ScrollContainerWidget>,
+            });
+        }
+
+        let old_offset = event_state.offset;
+        let new_offset = metrics.offset;
+        let source = event_state.source.take().unwrap_or_default();
+        let moved = new_offset != old_offset;
+
+        if moved {
+            event_state.offset = new_offset;
+            ew_scrolled.send(UiEvent {
+                widget: container,
+                action: Scrolled {
+                    old_offset,
+                    new_offset,
+                    source,
+                },
+                element_kind: PhantomData::<ScrollContainerWidget>,
+            });
+        }
+
+        // Edges are tracked even when nothing moved, so a container that starts overflowing
+        // at offset zero still reports reaching its end later on.
+        let axes = [
+            (ScrollAxis::Horizontal, overflowing.x, new_offset.x, scroll_threshold.x),
+            (ScrollAxis::Vertical, overflowing.y, new_offset.y, scroll_threshold.y),
+        ];
+
+        let mut at_start = [event_state.at_start.x, event_state.at_start.y];
+        let mut at_end = [event_state.at_end.x, event_state.at_end.y];
+
+        for (index, (axis, overflowing, offset, threshold)) in axes.into_iter().enumerate() {
+            let reached_start = !overflowing || offset <= 0.0;
+            let reached_end = !overflowing || offset >= threshold - EDGE_TOLERANCE;
+
+            if moved && overflowing && reached_start && !at_start[index] {
+                ew_reached_edge.send(UiEvent {
+                    widget: container,
+                    action: ReachedEdge { axis, edge: ScrollEdge::Start },
+                    element_kind: PhantomData::<ScrollContainerWidget>,
+                });
+            }
+
+            if moved && overflowing && reached_end && !at_end[index] {
+                ew_reached_edge.send(UiEvent {
+                    widget: container,
+                    action: ReachedEdge { axis, edge: ScrollEdge::End },
+                    element_kind: PhantomData::<ScrollContainerWidget>,
+                });
             }
+
+            at_start[index] = reached_start;
+            at_end[index] = reached_end;
+        }
+
+        let at_start = BVec2::new(at_start[0], at_start[1]);
+        let at_end = BVec2::new(at_end[0], at_end[1]);
+
+        if event_state.at_start != at_start || event_state.at_end != at_end {
+            event_state.at_start = at_start;
+            event_state.at_end = at_end;
         }
     }
 }
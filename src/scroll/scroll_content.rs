@@ -1,33 +1,73 @@
-use std::{marker::PhantomData, ops::Neg};
+use std::{collections::HashMap, marker::PhantomData};
 
 use super::components::*;
-use super::ScrollSystemSet;
-use super::scroll_indicator::ScrollIndicatorWidget;
+use super::{PropagateSchedule, ScrollSystemSet};
+use super::scroll_container::{ScrollContainerElement, ScrollContainerWidget};
+use super::scroll_theme::{scroll_theme, ScrollTheme};
+use super::scroll_indicator::{
+    autoscroll_settings, AutoscrollSettings, ScrollIndicatorMode, ScrollIndicatorWidget,
+};
 use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+        system::SystemParam,
+    },
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
-    ui::RelativeCursorPosition,
+    render::camera::NormalizedRenderTarget,
+    ui::{CalculatedClip, FocusPolicy, RelativeCursorPosition, UiStack},
+    window::PrimaryWindow,
 };
 
 use super::styles::{scroll_content_style, scroll_wrapper_style};
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, Default, Reflect, FromReflect)]
+#[reflect(Component, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub struct ScrollContentWidget {
     pub scroll_direction: ScrollDirection,
+    pub overscroll_behavior: OverscrollBehavior,
+    pub camera: Option<Entity>,
     pub current_offset: Vec2,
 }
 
+impl MapEntities for ScrollContentWidget {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        if let Some(camera) = self.camera.as_mut() {
+            if let Ok(mapped_camera) = entity_map.get(*camera) {
+                *camera = mapped_camera;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct ScrollContentElement {
     pub scroll_content: Entity,
     pub scroll_wrapper: Entity,
 }
 
+// What the scroll events last reported for a content widget, and what moved it since.
+#[derive(Component, Clone, Debug, Default, Reflect, FromReflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollEventState {
+    pub offset: Vec2,
+    pub source: Option<ScrollSource>,
+    pub overflowing: BVec2,
+    pub at_start: BVec2,
+    pub at_end: BVec2,
+}
+
 pub struct ScrollContentPlugin;
 
 impl Plugin for ScrollContentPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<ScrollContentWidget>()
+            .register_type::<ScrollEventState>()
             .add_event::<UiEvent<ScrollContentWidget, ScrollInput>>()
             .add_event::<UiEvent<ScrollContentWidget, ScrollIndicatorEnabled>>()
             .add_system(init_scroll_content.in_set(ScrollSystemSet::Create))
@@ -37,7 +77,12 @@ impl Plugin for ScrollContentPlugin {
             .add_system(scroll_content_indcator_enabled_subscriber.in_set(ScrollSystemSet::Update))
             .add_system(scroll_from_scroll_indicator.in_set(ScrollSystemSet::Update))
             .add_system(constrain_scroll_content.in_set(ScrollSystemSet::Constrain))
-            .add_system(extract_scroll_content.in_set(ScrollSystemSet::Extract));
+            .add_system(
+                constrain_scroll_content
+                    .in_set(ScrollSystemSet::Constrain)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(offset_scroll_content.in_set(ScrollSystemSet::PostLayout));
     }
 }
 
@@ -45,13 +90,28 @@ const PIXELS_SCROLLED_PER_LINE: f32 = 15.0;
 
 pub fn init_scroll_content(
     q_uninitialized_widgets: Query<(Entity, &ScrollContentWidget), Without<ScrollContentElement>>,
+    q_themes: Query<&ScrollTheme>,
+    global_theme: Res<ScrollTheme>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_uninitialized_containers: Query<(), (With<ScrollContainerWidget>, Without<ScrollContainerElement>)>,
+    q_parents: Query<&Parent>,
     mut commands: Commands,
 ) {
     for (widget_entity, widget) in q_uninitialized_widgets.iter() {
+        // Widgets loaded with their container wait for it to build the frame they go in.
+        if q_parents
+            .get(widget_entity)
+            .map_or(false, |parent| q_uninitialized_containers.contains(parent.get()))
+        {
+            continue;
+        }
+
+        let theme = scroll_theme(widget_entity, &q_themes, &q_parents, &global_theme);
+
         let scroll_content = commands
             .spawn(NodeBundle {
                 style: scroll_content_style(widget.scroll_direction),
-                background_color: BackgroundColor(Color::rgb(0.25, 0.25, 0.25)),
+                background_color: BackgroundColor(theme.content_background),
                 ..default()
             })
             .insert(ControlledByElement {
@@ -63,7 +123,7 @@ pub fn init_scroll_content(
         let scroll_wrapper = commands
             .spawn(NodeBundle {
                 style: scroll_wrapper_style(widget.scroll_direction),
-                background_color: BackgroundColor(Color::rgb(0.15, 0.15, 0.15)),
+                background_color: BackgroundColor(theme.wrapper_background),
                 ..default()
             })
             .insert(Interaction::None)
@@ -75,142 +135,370 @@ pub fn init_scroll_content(
             .id();
 
         commands.entity(scroll_wrapper).add_child(scroll_content);
-        commands.entity(widget_entity).insert(ScrollContentElement {
-            scroll_content,
-            scroll_wrapper,
-        });
-
-        for n in 1..51 {
-            let scroll_content_row = commands.spawn(NodeBundle {
-                style: Style {
-                    flex_direction: FlexDirection::Row,
-                    ..default()
-                },
-                ..default()
-            }).id();
-
-            commands
-                .entity(scroll_content)
-                .add_child(scroll_content_row);
 
-            for i in 1..51 {
-                generate_filler_content(&mut commands, scroll_content_row, n * i, 51.0 * 51.0);
-            }
+        if let Some(container) = widget_or_parent(widget_entity, &q_container_elements, &q_parents) {
+            commands.entity(container.scroll_frame).add_child(scroll_wrapper);
         }
+        commands.entity(widget_entity).insert((
+            ScrollContentElement {
+                scroll_content,
+                scroll_wrapper,
+            },
+            ScrollEventState {
+                offset: widget.current_offset,
+                at_start: BVec2::TRUE,
+                at_end: BVec2::TRUE,
+                ..default()
+            },
+        ));
     }
 }
 
-pub fn extract_scroll_content(
-    q_scroll_content_elements: Query<
-        (&ScrollContentElement, &ScrollContentWidget),
-        Or<(Changed<ScrollContentWidget>, Added<ScrollContentElement>)>,
-    >,
-    mut q_nodes: Query<&mut Style>,
+// Layout writes the content's translation every frame, so the offset is applied on top of it
+// afterwards. Scrolling never touches the content's Style and never triggers a relayout.
+pub fn offset_scroll_content(
+    q_scroll_content_elements: Query<(&ScrollContentElement, &ScrollContentWidget)>,
+    mut q_transforms: Query<&mut Transform>,
 ) {
     for (elem, widget) in q_scroll_content_elements.iter() {
-        let mut scroll_content_style = q_nodes
+        let mut scroll_content_transform = q_transforms
             .get_mut(elem.scroll_content)
-            .expect("ScrollContentElement.scroll_content should have been a (Style, Node).");
+            .expect("ScrollContentElement.scroll_content should have been a (Transform, Node).");
 
-        let scroll_margins = widget.current_offset.neg();
+        let scroll_offset = match widget.scroll_direction {
+            ScrollDirection::Vertical => Vec2::new(0.0, widget.current_offset.y),
+            ScrollDirection::Horizontal => Vec2::new(widget.current_offset.x, 0.0),
+            ScrollDirection::Both => widget.current_offset,
+            ScrollDirection::Neither => Vec2::ZERO,
+        };
 
-        match widget.scroll_direction {
-            ScrollDirection::Vertical => {
-                scroll_content_style.margin.top = Val::Px(scroll_margins.y);
-            }
-            ScrollDirection::Horizontal => {
-                scroll_content_style.margin.left = Val::Px(scroll_margins.x);
-            }
-            ScrollDirection::Both => {
-                scroll_content_style.margin.top = Val::Px(scroll_margins.y);
-                scroll_content_style.margin.left = Val::Px(scroll_margins.x);
-            }
-            ScrollDirection::Neither => {}
+        if scroll_offset != Vec2::ZERO {
+            scroll_content_transform.translation -= scroll_offset.extend(0.0);
         }
     }
 }
 
 pub fn scroll_content_scroll_publisher(
-    q_scroll_content_nodes: Query<(&ControlledByElement<ScrollContentElement>, &Interaction)>,
+    hit_test: ScrollHitTest,
+    q_scroll_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    q_nodes: Query<&Node>,
     mut er_mousewheel: EventReader<MouseWheel>,
     mut ew_scroll_content_mousewheel: EventWriter<UiEvent<ScrollContentWidget, ScrollInput>>,
 ) {
-    for (controller, scroll_content_interaction) in q_scroll_content_nodes.iter() {
-        if *scroll_content_interaction != Interaction::Hovered {
-            continue;
-        }
+    let chain = if let Some((window, cursor_position)) = hit_test.hovered_window() {
+        hit_test.topmost_scroll_chain(window, cursor_position)
+    } else {
+        Vec::new()
+    };
+
+    if chain.is_empty() {
+        er_mousewheel.clear();
+        return;
+    }
 
-        for mousewheel_event in er_mousewheel.iter() {
-            let distance = match mousewheel_event.unit {
-                MouseScrollUnit::Line => {
-                    PIXELS_SCROLLED_PER_LINE
-                        * Vec2 {
-                            x: mousewheel_event.x,
-                            y: mousewheel_event.y,
-                        }
-                }
-                MouseScrollUnit::Pixel => Vec2 {
-                    x: mousewheel_event.x,
-                    y: mousewheel_event.y,
-                },
+    let mut pending_offsets: HashMap<Entity, Vec2> = HashMap::new();
+
+    for mousewheel_event in er_mousewheel.iter() {
+        let distance = match mousewheel_event.unit {
+            MouseScrollUnit::Line => {
+                PIXELS_SCROLLED_PER_LINE
+                    * Vec2 {
+                        x: mousewheel_event.x,
+                        y: mousewheel_event.y,
+                    }
+            }
+            MouseScrollUnit::Pixel => Vec2 {
+                x: mousewheel_event.x,
+                y: mousewheel_event.y,
+            },
+        };
+
+        let mut remaining = distance;
+
+        for widget_entity in chain.iter() {
+            let (widget, element) =
+                if let Ok(widget) = q_scroll_content_widgets.get(*widget_entity) {
+                    widget
+                } else {
+                    continue;
+                };
+
+            let (content_node, wrapper_node) = if let (Ok(content_node), Ok(wrapper_node)) = (
+                q_nodes.get(element.scroll_content),
+                q_nodes.get(element.scroll_wrapper),
+            ) {
+                (content_node, wrapper_node)
+            } else {
+                continue;
             };
 
-            ew_scroll_content_mousewheel.send(UiEvent {
-                widget: controller.element,
-                action: ScrollInput { distance },
-                element_kind: PhantomData::<ScrollContentWidget>,
-            });
+            let scroll_threshold = (content_node.size() - wrapper_node.size()).max(Vec2::ZERO);
+            let offset = *pending_offsets
+                .entry(*widget_entity)
+                .or_insert(widget.current_offset);
+            let scrollable_axes = Vec2 {
+                x: if widget.scroll_direction.scrolls_x() { 1.0 } else { 0.0 },
+                y: if widget.scroll_direction.scrolls_y() { 1.0 } else { 0.0 },
+            };
+
+            let new_offset = (offset - remaining * scrollable_axes)
+                .min(scroll_threshold)
+                .max(Vec2::ZERO);
+            let consumed = offset - new_offset;
+
+            if consumed != Vec2::ZERO {
+                pending_offsets.insert(*widget_entity, new_offset);
+                ew_scroll_content_mousewheel.send(UiEvent {
+                    widget: *widget_entity,
+                    action: ScrollInput {
+                        distance: consumed,
+                        source: ScrollSource::Wheel,
+                    },
+                    element_kind: PhantomData::<ScrollContentWidget>,
+                });
+            }
+
+            remaining -= consumed;
+
+            match widget.overscroll_behavior {
+                OverscrollBehavior::Chain => {}
+                OverscrollBehavior::Contain => remaining *= Vec2::ONE - scrollable_axes,
+                OverscrollBehavior::None => break,
+            }
+
+            if remaining == Vec2::ZERO {
+                break;
+            }
         }
     }
 }
 
-pub fn scroll_content_indicator_publisher(
-    q_scroll_content_nodes: Query<(&ControlledByElement<ScrollContentElement>, &Interaction)>,
-    mouse_button_input: ResMut<Input<MouseButton>>,
-    window_query: Query<&Window>,
-    mut ew_scroll_content_indicator_enabled: EventWriter<
-        UiEvent<ScrollContentWidget, ScrollIndicatorEnabled>,
-    >
-) {
+#[derive(SystemParam)]
+pub struct ScrollHitTest<'w, 's> {
+    q_scroll_wrapper_nodes: Query<
+        'w,
+        's,
+        (Entity, &'static ControlledByElement<ScrollContentElement>),
+        With<Interaction>,
+    >,
+    q_scroll_content_widgets: Query<'w, 's, &'static ScrollContentWidget>,
+    q_scroll_frames: Query<'w, 's, &'static ControlledByElement<ScrollContainerElement>>,
+    q_scroll_container_widgets: Query<'w, 's, &'static ScrollContainerWidget>,
+    q_hit_nodes: Query<
+        'w,
+        's,
+        (
+            &'static Node,
+            &'static GlobalTransform,
+            Option<&'static FocusPolicy>,
+            Option<&'static CalculatedClip>,
+            Option<&'static ComputedVisibility>,
+        ),
+    >,
+    q_parents: Query<'w, 's, &'static Parent>,
+    q_cameras: Query<'w, 's, &'static Camera>,
+    q_windows: Query<'w, 's, (Entity, &'static Window)>,
+    q_primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    ui_stack: Res<'w, UiStack>,
+}
 
-    for (controller, scroll_content_interaction) in q_scroll_content_nodes.iter() {
-        if *scroll_content_interaction != Interaction::Hovered {
-            continue;
+impl<'w, 's> ScrollHitTest<'w, 's> {
+    // The window the cursor is over, and the cursor position in it with a top-left origin.
+    // Focused windows win if the OS reports a cursor over more than one.
+    pub fn hovered_window(&self) -> Option<(Entity, Vec2)> {
+        self.q_windows
+            .iter()
+            .filter_map(|(entity, window)| {
+                ui_cursor_position(window).map(|position| (entity, window.focused, position))
+            })
+            .max_by_key(|(_, focused, _)| *focused)
+            .map(|(entity, _, position)| (entity, position))
+    }
+
+    pub fn widget_window(&self, widget: Entity) -> Option<Entity> {
+        let widget = self.q_scroll_content_widgets.get(widget).ok()?;
+
+        resolve_scroll_window(
+            widget.camera,
+            &self.q_cameras,
+            self.q_primary_window.get_single().ok(),
+        )
+    }
+
+    // The window a node outside any scroll wrapper is drawn in: its container's when it's part
+    // of a frame, the primary window otherwise.
+    fn node_window(&self, entity: Entity) -> Option<Entity> {
+        let mut current = Some(entity);
+
+        while let Some(entity) = current {
+            if let Ok(frame) = self.q_scroll_frames.get(entity) {
+                let camera = self
+                    .q_scroll_container_widgets
+                    .get(frame.element)
+                    .ok()
+                    .and_then(|container_widget| container_widget.camera);
+
+                return resolve_scroll_window(
+                    camera,
+                    &self.q_cameras,
+                    self.q_primary_window.get_single().ok(),
+                );
+            }
+
+            current = self.q_parents.get(entity).ok().map(|parent| parent.get());
         }
 
-        if mouse_button_input.just_pressed(MouseButton::Middle) {
-            let window = window_query.single();
+        self.q_primary_window.get_single().ok()
+    }
 
-            let coords = if let Some(world_position) = window.cursor_position() {
-                Vec2 {
-                    x: world_position.x,
-                    y: window.height() - world_position.y,
-                }
-            } else {
-                return;
+    // Walks the UI stack from the top down, the same way `ui_focus_system` does, and returns
+    // the scroll chain of the first node under the cursor that sits inside a scroll wrapper
+    // belonging to `window`. A node of `window` outside any wrapper stops the walk unless its
+    // `FocusPolicy` is `Pass`; nodes of other windows are skipped.
+    //
+    // No scale factor is applied: the cursor position and the laid out node sizes and
+    // transforms are all in logical pixels, whatever the window's scale factor.
+    pub fn topmost_scroll_chain(&self, window: Entity, cursor_position: Vec2) -> Vec<Entity> {
+        let wrapper_widgets: HashMap<Entity, Entity> = self
+            .q_scroll_wrapper_nodes
+            .iter()
+            .map(|(wrapper, controller)| (wrapper, controller.element))
+            .collect();
+
+        for entity in self.ui_stack.uinodes.iter().rev() {
+            let (node, transform, focus_policy, clip, visibility) =
+                if let Ok(hit_node) = self.q_hit_nodes.get(*entity) {
+                    hit_node
+                } else {
+                    continue;
+                };
+
+            if visibility.map_or(false, |visibility| !visibility.is_visible()) {
+                continue;
+            }
+
+            let mut rect = Rect::from_center_size(transform.translation().truncate(), node.size());
+            if let Some(clip) = clip {
+                rect = rect.intersect(clip.clip);
+            }
+
+            if !rect.contains(cursor_position) {
+                continue;
+            }
+
+            let chain = self.scroll_chain(*entity, &wrapper_widgets);
+            let node_window = match chain.first() {
+                Some(innermost) => self.widget_window(*innermost),
+                None => self.node_window(*entity),
             };
+            if node_window != Some(window) {
+                continue;
+            }
+
+            if !chain.is_empty() {
+                return chain
+                    .into_iter()
+                    .filter(|widget| self.widget_window(*widget) == Some(window))
+                    .collect();
+            }
 
-            ew_scroll_content_indicator_enabled.send(UiEvent {
-                widget: controller.element,
-                action: ScrollIndicatorEnabled { position: coords },
-                element_kind: PhantomData::<ScrollContentWidget>,
-            });
+            if *focus_policy.unwrap_or(&FocusPolicy::Block) == FocusPolicy::Block {
+                break;
+            }
+        }
+
+        Vec::new()
+    }
 
-            return;
+    // Scroll content widgets whose wrappers enclose `entity`, innermost first.
+    fn scroll_chain(
+        &self,
+        entity: Entity,
+        wrapper_widgets: &HashMap<Entity, Entity>,
+    ) -> Vec<Entity> {
+        let mut chain = Vec::new();
+        let mut current = Some(entity);
+
+        while let Some(entity) = current {
+            if let Some(widget) = wrapper_widgets.get(&entity) {
+                chain.push(*widget);
+            }
+
+            current = self.q_parents.get(entity).ok().map(|parent| parent.get());
         }
+
+        chain
+    }
+}
+
+// Containers without a camera live in the primary window. Cameras rendering to an image
+// have no window, so their containers never receive cursor input.
+pub fn resolve_scroll_window(
+    camera: Option<Entity>,
+    q_cameras: &Query<&Camera>,
+    primary_window: Option<Entity>,
+) -> Option<Entity> {
+    let camera = if let Some(camera) = camera {
+        camera
+    } else {
+        return primary_window;
+    };
+
+    match q_cameras.get(camera).ok()?.target.normalize(primary_window)? {
+        NormalizedRenderTarget::Window(window_ref) => Some(window_ref.entity()),
+        NormalizedRenderTarget::Image(_) => None,
+    }
+}
+
+pub fn ui_cursor_position(window: &Window) -> Option<Vec2> {
+    window.cursor_position().map(|position| Vec2 {
+        x: position.x,
+        y: window.height() - position.y,
+    })
+}
+
+pub fn scroll_content_indicator_publisher(
+    hit_test: ScrollHitTest,
+    q_scroll_indicators: Query<(), With<ScrollIndicatorWidget>>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    mut ew_scroll_content_indicator_enabled: EventWriter<
+        UiEvent<ScrollContentWidget, ScrollIndicatorEnabled>,
+    >
+) {
+    // The click that ends a toggled autoscroll shouldn't start a new one.
+    if !mouse_button_input.just_pressed(MouseButton::Middle) || !q_scroll_indicators.is_empty() {
+        return;
+    }
+
+    let (window, coords) = if let Some(hovered_window) = hit_test.hovered_window() {
+        hovered_window
+    } else {
+        return;
+    };
+
+    if let Some(widget) = hit_test.topmost_scroll_chain(window, coords).first() {
+        ew_scroll_content_indicator_enabled.send(UiEvent {
+            widget: *widget,
+            action: ScrollIndicatorEnabled { position: coords, window },
+            element_kind: PhantomData::<ScrollContentWidget>,
+        });
     }
 }
 
 pub fn scroll_content_scroll_subscriber(
     mut er_scroll_content_scroll: EventReader<UiEvent<ScrollContentWidget, ScrollInput>>,
-    mut q_scroll_content_widgets: Query<&mut ScrollContentWidget, With<ScrollContentElement>>,
+    mut q_scroll_content_widgets: Query<
+        (&mut ScrollContentWidget, &mut ScrollEventState),
+        With<ScrollContentElement>,
+    >,
 ) {
     for event in er_scroll_content_scroll.iter() {
-        let mut widget = q_scroll_content_widgets
+        let (mut widget, mut event_state) = q_scroll_content_widgets
             .get_mut(event.widget)
             .expect("ScrollContentWidget event should have contained a ScrollContentWidget.");
 
         widget.current_offset -= event.action.distance;
+        event_state.source = Some(event.action.source);
     }
 }
 
@@ -226,46 +514,64 @@ pub fn scroll_content_indcator_enabled_subscriber(
 
         commands.spawn(ScrollIndicatorWidget {
             scroll_direction: widget.scroll_direction,
-            absolute_position: event.action.position - Vec2 { x: 16.0, y: 16.0 },   // dirty, get the actual node halfsize instead
+            absolute_position: event.action.position,
             scroll_content: event.widget,
+            window: event.action.window,
+            mode: ScrollIndicatorMode::Held,
+            moved: false,
         });
     }
 }
 
 pub fn scroll_from_scroll_indicator(
-    window_query: Query<&Window>,
+    q_windows: Query<&Window>,
     q_scroll_indicator: Query<&ScrollIndicatorWidget>,
+    q_autoscroll_settings: Query<&AutoscrollSettings>,
+    q_parents: Query<&Parent>,
+    global_autoscroll_settings: Res<AutoscrollSettings>,
+    time: Res<Time>,
     mut ew_scroll_content_scroll_input: EventWriter<UiEvent<ScrollContentWidget, ScrollInput>>
 ) {
-    let window = window_query.single();
-    
-    let cursor_coords = if let Some(world_position) = window.cursor_position() {
-        world_position
-    } else {
-        return;
-    };
-
     for widget in q_scroll_indicator.iter()
     {
-        let node_half_size = Vec2 { x: 16.0, y: -16.0 }; // dirty, get the actual node halfsize instead
+        let window = if let Ok(window) = q_windows.get(widget.window) {
+            window
+        } else {
+            continue;
+        };
+
+        let cursor_coords = if let Some(world_position) = window.cursor_position() {
+            world_position
+        } else {
+            continue;
+        };
+
         let indicator_coords = Vec2 {
             x: widget.absolute_position.x,
             y: window.height() - widget.absolute_position.y
-        } + node_half_size;
-        
+        };
 
-        let easing = CubicBezierEasing::new([0.35, 0.01], [0.97, 0.79]);
-        let delta = cursor_coords - indicator_coords;
+        let settings = autoscroll_settings(
+            widget.scroll_content,
+            &q_autoscroll_settings,
+            &q_parents,
+            &global_autoscroll_settings,
+        );
 
-        let window_dimensions = Vec2 { x: window.width(), y: window.height() };
-        let normalized_delta = delta / window_dimensions;
+        let delta = cursor_coords - indicator_coords;
+        let velocity = settings.velocity(delta);
+        let distance = velocity * time.delta_seconds();
 
-        let eased_delta_y =  delta.y * easing.ease(normalized_delta.y.abs().max(0.2));
-        let eased_delta_x = delta.x * easing.ease(normalized_delta.x.abs().max(0.2));
+        if distance == Vec2::ZERO {
+            continue;
+        }
 
         ew_scroll_content_scroll_input.send(UiEvent {
             widget: widget.scroll_content,
-            action: ScrollInput { distance: Vec2 { x: -eased_delta_x, y: eased_delta_y } },
+            action: ScrollInput {
+                distance: Vec2 { x: -distance.x, y: distance.y },
+                source: ScrollSource::Autoscroll,
+            },
             element_kind: PhantomData::<ScrollContentWidget>,
         });
     }
@@ -273,12 +579,12 @@ pub fn scroll_from_scroll_indicator(
 
 pub fn constrain_scroll_content(
     mut q_scroll_content_widget: Query<
-        (&mut ScrollContentWidget, &ScrollContentElement),
+        (&mut ScrollContentWidget, &ScrollContentElement, &mut ScrollEventState),
         Changed<ScrollContentWidget>,
     >,
     q_nodes: Query<&Node>,
 ) {
-    for (mut widget, element) in q_scroll_content_widget.iter_mut() {
+    for (mut widget, element, mut event_state) in q_scroll_content_widget.iter_mut() {
         let scroll_content_node = q_nodes
             .get(element.scroll_content)
             .expect("ScrollContentElement.scroll_bar should have contained a Node.");
@@ -291,41 +597,11 @@ pub fn constrain_scroll_content(
         let new_offset = scroll_threshold.min(widget.current_offset).max(Vec2::ZERO);
 
         if new_offset != widget.current_offset {
+            if event_state.source.is_none() && widget.current_offset == event_state.offset {
+                event_state.source = Some(ScrollSource::Layout);
+            }
+
             widget.current_offset = new_offset;
         }
     }
 }
-
-fn generate_filler_content(commands: &mut Commands, parent: Entity, number: u16, biggest_number: f32) {
-    let fixed_tick_node = NodeBundle {
-        style: Style {
-            min_size: Size {
-                width: Val::Px(40.0),
-                height: Val::Px(40.0),
-            },
-            size: Size {
-                width: Val::Px(40.0),
-                height: Val::Px(40.0),
-            },
-            margin: UiRect {
-                top: Val::Px(2.0),
-                left: Val::Px(2.0),
-                ..default()
-            },
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            ..default()
-        },
-        ..default()
-    };
-
-    commands.entity(parent).with_children(|container_panel_p| {
-        container_panel_p
-            .spawn(fixed_tick_node)
-            .insert(BackgroundColor(Color::rgb(
-                0.5,
-                1.0 - (f32::from(number) / biggest_number),
-                f32::from(number) / biggest_number,
-            )));
-    });
-}
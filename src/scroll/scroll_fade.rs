@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use super::components::*;
+use super::scroll_content::{ScrollContentElement, ScrollContentWidget};
+use super::styles::{scroll_fade_step_style, scroll_fade_style};
+use super::{PropagateSchedule, ScrollSystemSet};
+
+// Shades the edges of the viewport that have content hidden beyond them. Goes on a container
+// or its content widget.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollEdgeFade {
+    // How far the shading reaches into the viewport.
+    pub size: Val,
+    // The colour at the edge, fading out to transparent towards the centre.
+    pub color: Color,
+    // Hidden distance in logical pixels at which an edge is fully shaded, less fades it out.
+    pub fade_distance: f32,
+    // Bands the gradient is drawn with.
+    pub steps: usize,
+}
+
+impl Default for ScrollEdgeFade {
+    fn default() -> Self {
+        ScrollEdgeFade {
+            size: Val::Px(24.0),
+            color: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            fade_distance: 48.0,
+            steps: 8,
+        }
+    }
+}
+
+// The overlays built for the fade a content widget resolved to.
+#[derive(Component, Clone, Debug)]
+pub struct ScrollEdgeFadeElement {
+    pub fade: ScrollEdgeFade,
+    pub overlays: Vec<ScrollFadeOverlay>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScrollFadeOverlay {
+    pub axis: ScrollAxis,
+    pub edge: ScrollEdge,
+    pub root: Entity,
+    // Ordered from the edge inwards.
+    pub steps: Vec<Entity>,
+}
+
+pub struct ScrollFadePlugin;
+
+impl Plugin for ScrollFadePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollEdgeFade>()
+            .add_system(init_scroll_edge_fade.in_set(ScrollSystemSet::Create))
+            .add_system(extract_scroll_edge_fade.in_set(ScrollSystemSet::Extract))
+            .add_system(
+                extract_scroll_edge_fade
+                    .in_set(ScrollSystemSet::Extract)
+                    .in_schedule(PropagateSchedule),
+            );
+    }
+}
+
+// Rebuilds the overlays whenever the fade a content widget resolves to changes. They're added
+// to the wrapper after the content, so they're drawn over it without moving with it.
+pub fn init_scroll_edge_fade(
+    q_content_widgets: Query<(Entity, &ScrollContentElement, Option<&ScrollEdgeFadeElement>)>,
+    q_fades: Query<&ScrollEdgeFade>,
+    q_parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for (widget_entity, elem, fade_elem) in q_content_widgets.iter() {
+        let fade = widget_or_parent(widget_entity, &q_fades, &q_parents);
+        if fade_elem.map(|fade_elem| &fade_elem.fade) == fade {
+            continue;
+        }
+
+        if let Some(fade_elem) = fade_elem {
+            for overlay in &fade_elem.overlays {
+                commands.entity(overlay.root).despawn_recursive();
+            }
+        }
+
+        let Some(fade) = fade else {
+            commands.entity(widget_entity).remove::<ScrollEdgeFadeElement>();
+            continue;
+        };
+
+        let edges = [
+            (ScrollAxis::Vertical, ScrollEdge::Start),
+            (ScrollAxis::Vertical, ScrollEdge::End),
+            (ScrollAxis::Horizontal, ScrollEdge::Start),
+            (ScrollAxis::Horizontal, ScrollEdge::End),
+        ];
+
+        let overlays = edges
+            .into_iter()
+            .map(|(axis, edge)| {
+                let steps: Vec<Entity> = (0..fade.steps.max(1))
+                    .map(|_| {
+                        commands
+                            .spawn(NodeBundle {
+                                style: scroll_fade_step_style(),
+                                focus_policy: FocusPolicy::Pass,
+                                ..default()
+                            })
+                            .id()
+                    })
+                    .collect();
+
+                let root = commands
+                    .spawn(NodeBundle {
+                        style: scroll_fade_style(axis, edge, fade.size),
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    })
+                    .push_children(&steps)
+                    .id();
+
+                commands.entity(elem.scroll_wrapper).add_child(root);
+
+                ScrollFadeOverlay {
+                    axis,
+                    edge,
+                    root,
+                    steps,
+                }
+            })
+            .collect();
+
+        commands.entity(widget_entity).insert(ScrollEdgeFadeElement {
+            fade: fade.clone(),
+            overlays,
+        });
+    }
+}
+
+// Each edge is shaded by how much content is hidden past it, between the current offset and
+// the furthest one `constrain_scroll_content` allows.
+pub fn extract_scroll_edge_fade(
+    q_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement, &ScrollEdgeFadeElement)>,
+    q_nodes: Query<&Node>,
+    mut q_colors: Query<&mut BackgroundColor>,
+) {
+    for (widget, elem, fade_elem) in q_content_widgets.iter() {
+        let (Ok(content_node), Ok(wrapper_node)) =
+            (q_nodes.get(elem.scroll_content), q_nodes.get(elem.scroll_wrapper))
+        else {
+            continue;
+        };
+
+        let max_offset = (content_node.size() - wrapper_node.size()).max(Vec2::ZERO);
+        let fade = &fade_elem.fade;
+
+        for overlay in &fade_elem.overlays {
+            let (scrolls, offset, max_offset) = match overlay.axis {
+                ScrollAxis::Horizontal => (
+                    widget.scroll_direction.scrolls_x(),
+                    widget.current_offset.x,
+                    max_offset.x,
+                ),
+                ScrollAxis::Vertical => (
+                    widget.scroll_direction.scrolls_y(),
+                    widget.current_offset.y,
+                    max_offset.y,
+                ),
+            };
+
+            let hidden = match overlay.edge {
+                ScrollEdge::Start => offset,
+                ScrollEdge::End => max_offset - offset,
+            };
+
+            let strength = match scrolls {
+                true => (hidden / fade.fade_distance.max(f32::EPSILON)).clamp(0.0, 1.0),
+                false => 0.0,
+            };
+
+            let step_count = overlay.steps.len() as f32;
+            for (index, step) in overlay.steps.iter().enumerate() {
+                let ramp = 1.0 - index as f32 / step_count;
+                let mut color = fade.color;
+                color.set_a(fade.color.a() * ramp * strength);
+
+                if let Ok(mut background_color) = q_colors.get_mut(*step) {
+                    if background_color.0 != color {
+                        background_color.0 = color;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,65 @@
-use std::marker::PhantomData;
+use std::{collections::HashSet, marker::PhantomData};
 
 use super::components::*;
+use super::scroll_content::ui_cursor_position;
+use super::scroll_theme::ScrollTheme;
 use super::ScrollSystemSet;
-use bevy::prelude::*;
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
 
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Debug, Reflect, FromReflect)]
+#[reflect(Component, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
 pub struct ScrollIndicatorWidget {
     pub scroll_direction: ScrollDirection,
     pub absolute_position: Vec2,
     pub scroll_content: Entity,
+    pub window: Entity,
+    pub mode: ScrollIndicatorMode,
+    // Whether the cursor has left the toggle threshold since autoscroll started.
+    pub moved: bool,
+}
+
+// Only used by reflection, indicators are always spawned by the content they scroll.
+impl FromWorld for ScrollIndicatorWidget {
+    fn from_world(_world: &mut World) -> Self {
+        ScrollIndicatorWidget {
+            scroll_direction: ScrollDirection::default(),
+            absolute_position: Vec2::ZERO,
+            scroll_content: Entity::PLACEHOLDER,
+            window: Entity::PLACEHOLDER,
+            mode: ScrollIndicatorMode::default(),
+            moved: false,
+        }
+    }
+}
+
+// The window is usually outside the loaded scene, and keeps its entity then.
+impl MapEntities for ScrollIndicatorWidget {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        if let Ok(mapped_content) = entity_map.get(self.scroll_content) {
+            self.scroll_content = mapped_content;
+        }
+        if let Ok(mapped_window) = entity_map.get(self.window) {
+            self.window = mapped_window;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollIndicatorMode {
+    // The middle button is still down; releasing it ends autoscroll.
+    #[default]
+    Held,
+    // The middle button was released without moving; the next click or keypress ends autoscroll.
+    Toggled,
 }
 
 #[derive(Component, Clone, Debug)]
@@ -16,15 +67,204 @@ pub struct ScrollIndicatorElement {
     scroll_indicator: Entity,
 }
 
+#[derive(Resource, Clone, Debug, Reflect, FromReflect)]
+#[reflect(Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollIndicatorSettings {
+    // Size of the indicator image in UI pixels. Uses the texture's own size when `None`.
+    pub size: Option<Vec2>,
+    // How far the cursor may move, in logical pixels, before releasing the middle button
+    // ends autoscroll instead of toggling it on.
+    pub toggle_threshold: f32,
+    // Hides the OS cursor while an indicator is shown in its window.
+    pub hide_cursor: bool,
+}
+
+impl Default for ScrollIndicatorSettings {
+    fn default() -> Self {
+        ScrollIndicatorSettings {
+            size: None,
+            toggle_threshold: 10.0,
+            hide_cursor: false,
+        }
+    }
+}
+
+// Global autoscroll tuning. Insert it as a component on a scroll container to override it
+// for that container alone.
+#[derive(Resource, Component, Clone, Debug, Reflect, FromReflect)]
+#[reflect(Component, Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct AutoscrollSettings {
+    // Distance from the neutral point, in logical pixels, inside which nothing scrolls.
+    pub dead_zone_radius: f32,
+    // Speed in pixels per second once the cursor is `full_speed_distance` past the dead zone.
+    pub max_speed: f32,
+    pub full_speed_distance: f32,
+    pub easing: ScrollEasing,
+}
+
+impl Default for AutoscrollSettings {
+    fn default() -> Self {
+        AutoscrollSettings {
+            dead_zone_radius: 10.0,
+            max_speed: 3000.0,
+            full_speed_distance: 400.0,
+            easing: ScrollEasing::CubicBezier {
+                p1: Vec2 { x: 0.35, y: 0.01 },
+                p2: Vec2 { x: 0.97, y: 0.79 },
+            },
+        }
+    }
+}
+
+impl AutoscrollSettings {
+    // Signed scroll speed per axis, in pixels per second, for a cursor `delta` away from the
+    // neutral point.
+    pub fn velocity(&self, delta: Vec2) -> Vec2 {
+        if delta.length() <= self.dead_zone_radius {
+            return Vec2::ZERO;
+        }
+
+        let axis_velocity = |axis_delta: f32| {
+            let progress = ((axis_delta.abs() - self.dead_zone_radius)
+                / self.full_speed_distance.max(f32::EPSILON))
+            .clamp(0.0, 1.0);
+
+            axis_delta.signum() * self.max_speed * self.easing.ease(progress)
+        };
+
+        Vec2 {
+            x: axis_velocity(delta.x),
+            y: axis_velocity(delta.y),
+        }
+    }
+}
+
+// The settings on the content widget or its container, falling back to the global resource.
+pub fn autoscroll_settings<'a>(
+    scroll_content: Entity,
+    q_autoscroll_settings: &'a Query<&AutoscrollSettings>,
+    q_parents: &Query<&Parent>,
+    global_autoscroll_settings: &'a AutoscrollSettings,
+) -> &'a AutoscrollSettings {
+    widget_or_parent(scroll_content, q_autoscroll_settings, q_parents)
+        .unwrap_or(global_autoscroll_settings)
+}
+
+fn indicator_textures<'a>(
+    scroll_content: Entity,
+    q_themes: &'a Query<&ScrollTheme>,
+    q_parents: &Query<&Parent>,
+    global_theme: &'a ScrollTheme,
+    default_textures: &'a ScrollIndicatorTextures,
+) -> &'a ScrollIndicatorTextures {
+    widget_or_parent(scroll_content, q_themes, q_parents)
+        .and_then(|theme| theme.indicator.as_ref())
+        .or(global_theme.indicator.as_ref())
+        .unwrap_or(default_textures)
+}
+
+#[derive(Resource, Clone, Debug, Reflect, FromReflect)]
+pub struct ScrollIndicatorTextures {
+    pub vertical: Handle<Image>,
+    pub horizontal: Handle<Image>,
+    pub multi: Handle<Image>,
+    pub north: Handle<Image>,
+    pub north_east: Handle<Image>,
+    pub east: Handle<Image>,
+    pub south_east: Handle<Image>,
+    pub south: Handle<Image>,
+    pub south_west: Handle<Image>,
+    pub west: Handle<Image>,
+    pub north_west: Handle<Image>,
+}
+
+impl FromWorld for ScrollIndicatorTextures {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+
+        ScrollIndicatorTextures {
+            vertical: asset_server.load("sprites/scroll_cursor_vert.png"),
+            horizontal: asset_server.load("sprites/scroll_cursor_horiz.png"),
+            multi: asset_server.load("sprites/scroll_cursor_multi.png"),
+            north: asset_server.load("sprites/scroll_cursor_n.png"),
+            north_east: asset_server.load("sprites/scroll_cursor_ne.png"),
+            east: asset_server.load("sprites/scroll_cursor_e.png"),
+            south_east: asset_server.load("sprites/scroll_cursor_se.png"),
+            south: asset_server.load("sprites/scroll_cursor_s.png"),
+            south_west: asset_server.load("sprites/scroll_cursor_sw.png"),
+            west: asset_server.load("sprites/scroll_cursor_w.png"),
+            north_west: asset_server.load("sprites/scroll_cursor_nw.png"),
+        }
+    }
+}
+
+impl ScrollIndicatorTextures {
+    // A container that scrolls neither way shows the multi-direction cursor, same as `Both`.
+    pub fn neutral(&self, scroll_direction: ScrollDirection) -> Handle<Image> {
+        match scroll_direction {
+            ScrollDirection::Vertical => self.vertical.clone(),
+            ScrollDirection::Horizontal => self.horizontal.clone(),
+            ScrollDirection::Both | ScrollDirection::Neither => self.multi.clone(),
+        }
+    }
+
+    // `delta` is the cursor's offset from the neutral point, with y pointing down.
+    pub fn heading(
+        &self,
+        scroll_direction: ScrollDirection,
+        delta: Vec2,
+        dead_zone_radius: f32,
+    ) -> Handle<Image> {
+        let delta = Vec2 {
+            x: if scroll_direction.scrolls_x() { delta.x } else { 0.0 },
+            y: if scroll_direction.scrolls_y() { delta.y } else { 0.0 },
+        };
+
+        if delta.length() <= dead_zone_radius {
+            return self.neutral(scroll_direction);
+        }
+
+        let octant = ((-delta.y).atan2(delta.x) / std::f32::consts::FRAC_PI_4).round() as i32;
+
+        match octant.rem_euclid(8) {
+            0 => self.east.clone(),
+            1 => self.north_east.clone(),
+            2 => self.north.clone(),
+            3 => self.north_west.clone(),
+            4 => self.west.clone(),
+            5 => self.south_west.clone(),
+            6 => self.south.clone(),
+            _ => self.south_east.clone(),
+        }
+    }
+}
+
 pub struct ScrollIndicatorPlugin;
 
 impl Plugin for ScrollIndicatorPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<ScrollIndicatorWidget>()
+            .register_type::<ScrollIndicatorMode>()
+            .register_type::<ScrollIndicatorSettings>()
+            .register_type::<AutoscrollSettings>()
+            .register_type::<ScrollIndicatorTextures>()
+            .register_type::<Option<ScrollIndicatorTextures>>()
+            .init_resource::<ScrollIndicatorSettings>()
+            .init_resource::<ScrollIndicatorTextures>()
+            .init_resource::<AutoscrollSettings>()
             .add_event::<UiEvent<ScrollIndicatorWidget, ScrollIndicatorDisabled>>()
+            .add_event::<UiEvent<ScrollIndicatorWidget, ScrollIndicatorToggled>>()
             .add_system(init_scroll_indicator.in_set(ScrollSystemSet::Create))
             .add_system(scroll_indicator_disabled_publisher.in_set(ScrollSystemSet::Interact))
+            .add_system(scroll_indicator_moved_tracker.in_set(ScrollSystemSet::Update))
+            .add_system(scroll_indicator_toggled_subscriber.in_set(ScrollSystemSet::Update))
             .add_system(scroll_indicator_disabled_subscriber.in_set(ScrollSystemSet::Update))
+            .add_system(extract_scroll_indicator.in_set(ScrollSystemSet::Extract))
+            .add_system(extract_scroll_indicator_heading.in_set(ScrollSystemSet::Extract))
+            .add_system(hide_cursor_for_scroll_indicator.in_set(ScrollSystemSet::Extract))
             .add_system(delete_scroll_indicator.in_set(ScrollSystemSet::Extract));
     }
 }
@@ -34,16 +274,17 @@ pub fn init_scroll_indicator(
         (Entity, &ScrollIndicatorWidget),
         Without<ScrollIndicatorElement>,
     >,
-    asset_server: Res<AssetServer>,
+    textures: Res<ScrollIndicatorTextures>,
+    q_themes: Query<&ScrollTheme>,
+    q_parents: Query<&Parent>,
+    global_theme: Res<ScrollTheme>,
+    settings: Res<ScrollIndicatorSettings>,
     mut commands: Commands,
 ) {
     for (entity, widget) in q_uninitialized_widgets.iter() {
-        let texture = match widget.scroll_direction {
-            ScrollDirection::Vertical => asset_server.load("sprites/scroll_cursor_vert.png"),
-            ScrollDirection::Horizontal => asset_server.load("sprites/scroll_cursor_horiz.png"),
-            ScrollDirection::Both => asset_server.load("sprites/scroll_cursor_multi.png"),
-            ScrollDirection::Neither => todo!(),
-        };
+        let textures =
+            indicator_textures(widget.scroll_content, &q_themes, &q_parents, &global_theme, &textures);
+        let texture = textures.neutral(widget.scroll_direction);
 
         let scroll_indicator = commands
             .spawn(ImageBundle {
@@ -53,18 +294,14 @@ pub fn init_scroll_indicator(
                 },
                 style: Style {
                     position_type: PositionType::Absolute,
-                    position: UiRect {
-                        left: Val::Px(widget.absolute_position.x),
-                        right: Val::Px(0.0),
-                        top: Val::Px(widget.absolute_position.y),
-                        bottom: Val::Px(0.0),
-                    },
-                    size: Size {
-                        width: Val::Px(32.0),
-                        height: Val::Px(32.0),
+                    size: match settings.size {
+                        Some(size) => Size::new(Val::Px(size.x), Val::Px(size.y)),
+                        None => Size::AUTO,
                     },
                     ..default()
                 },
+                // Hidden until layout has sized the node, see `extract_scroll_indicator`.
+                visibility: Visibility::Hidden,
                 ..default()
             })
             .insert(ControlledByElement {
@@ -79,20 +316,182 @@ pub fn init_scroll_indicator(
     }
 }
 
+// Centres the indicator on the point where autoscroll started, using its laid out size.
+pub fn extract_scroll_indicator(
+    q_scroll_indicators: Query<(&ScrollIndicatorWidget, &ScrollIndicatorElement)>,
+    mut q_nodes: Query<(&Node, &mut Style, &mut Visibility)>,
+    ui_scale: Res<UiScale>,
+) {
+    for (widget, element) in q_scroll_indicators.iter() {
+        let (node, mut style, mut visibility) = q_nodes
+            .get_mut(element.scroll_indicator)
+            .expect("ScrollIndicatorElement.scroll_indicator should have been a (Node, Style).");
+
+        if node.size() == Vec2::ZERO {
+            continue;
+        }
+
+        let top_left = (widget.absolute_position - node.size() / 2.0) / ui_scale.scale as f32;
+        let position = UiRect {
+            left: Val::Px(top_left.x),
+            top: Val::Px(top_left.y),
+            ..default()
+        };
+
+        if style.position != position {
+            style.position = position;
+        }
+
+        if *visibility != Visibility::Inherited {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+pub fn extract_scroll_indicator_heading(
+    q_scroll_indicators: Query<(&ScrollIndicatorWidget, &ScrollIndicatorElement)>,
+    q_windows: Query<&Window>,
+    q_autoscroll_settings: Query<&AutoscrollSettings>,
+    q_parents: Query<&Parent>,
+    global_autoscroll_settings: Res<AutoscrollSettings>,
+    mut q_images: Query<&mut UiImage>,
+    textures: Res<ScrollIndicatorTextures>,
+    q_themes: Query<&ScrollTheme>,
+    global_theme: Res<ScrollTheme>,
+) {
+    for (widget, element) in q_scroll_indicators.iter() {
+        let textures =
+            indicator_textures(widget.scroll_content, &q_themes, &q_parents, &global_theme, &textures);
+        let settings = autoscroll_settings(
+            widget.scroll_content,
+            &q_autoscroll_settings,
+            &q_parents,
+            &global_autoscroll_settings,
+        );
+
+        let cursor_position = q_windows
+            .get(widget.window)
+            .ok()
+            .and_then(ui_cursor_position);
+
+        let texture = match cursor_position {
+            Some(cursor_position) => textures.heading(
+                widget.scroll_direction,
+                cursor_position - widget.absolute_position,
+                settings.dead_zone_radius,
+            ),
+            None => textures.neutral(widget.scroll_direction),
+        };
+
+        let mut image = q_images
+            .get_mut(element.scroll_indicator)
+            .expect("ScrollIndicatorElement.scroll_indicator should have been a UiImage.");
+
+        if image.texture != texture {
+            image.texture = texture;
+        }
+    }
+}
+
+pub fn hide_cursor_for_scroll_indicator(
+    q_scroll_indicators: Query<&ScrollIndicatorWidget>,
+    mut q_windows: Query<(Entity, &mut Window)>,
+    settings: Res<ScrollIndicatorSettings>,
+    mut hidden_windows: Local<HashSet<Entity>>,
+) {
+    for (entity, mut window) in q_windows.iter_mut() {
+        let indicator_shown = settings.hide_cursor
+            && q_scroll_indicators
+                .iter()
+                .any(|widget| widget.window == entity);
+
+        if indicator_shown && window.cursor.visible {
+            window.cursor.visible = false;
+            hidden_windows.insert(entity);
+        } else if !indicator_shown && hidden_windows.remove(&entity) {
+            window.cursor.visible = true;
+        }
+    }
+}
+
 pub fn scroll_indicator_disabled_publisher(
     mut ew_scroll_content_indicator_disabled: EventWriter<
         UiEvent<ScrollIndicatorWidget, ScrollIndicatorDisabled>
     >,
-    q_scroll_indicators: Query<Entity, With<ScrollIndicatorWidget>>,
-    mouse_button_input: ResMut<Input<MouseButton>>,
+    mut ew_scroll_content_indicator_toggled: EventWriter<
+        UiEvent<ScrollIndicatorWidget, ScrollIndicatorToggled>
+    >,
+    q_scroll_indicators: Query<(Entity, &ScrollIndicatorWidget)>,
+    q_windows: Query<&Window>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
 ) {
-    if mouse_button_input.just_released(MouseButton::Middle) {
-        for entity in q_scroll_indicators.iter() {
+    for (entity, widget) in q_scroll_indicators.iter() {
+        // A release we never saw, e.g. while another window had focus, must not leave
+        // the content scrolling forever.
+        let lost_focus = q_windows
+            .get(widget.window)
+            .map_or(true, |window| !window.focused);
+
+        let disabled = lost_focus
+            || match widget.mode {
+                ScrollIndicatorMode::Held => {
+                    !mouse_button_input.pressed(MouseButton::Middle) && widget.moved
+                }
+                ScrollIndicatorMode::Toggled => {
+                    mouse_button_input.get_just_pressed().next().is_some()
+                        || keyboard_input.get_just_pressed().next().is_some()
+                }
+            };
+
+        if disabled {
             ew_scroll_content_indicator_disabled.send(UiEvent {
                 widget: entity,
                 action: ScrollIndicatorDisabled,
                 element_kind: PhantomData::<ScrollIndicatorWidget>,
             });
+        } else if widget.mode == ScrollIndicatorMode::Held
+            && !mouse_button_input.pressed(MouseButton::Middle)
+        {
+            ew_scroll_content_indicator_toggled.send(UiEvent {
+                widget: entity,
+                action: ScrollIndicatorToggled,
+                element_kind: PhantomData::<ScrollIndicatorWidget>,
+            });
+        }
+    }
+}
+
+pub fn scroll_indicator_moved_tracker(
+    mut q_scroll_indicators: Query<&mut ScrollIndicatorWidget>,
+    q_windows: Query<&Window>,
+    settings: Res<ScrollIndicatorSettings>,
+) {
+    for mut widget in q_scroll_indicators.iter_mut() {
+        if widget.moved {
+            continue;
+        }
+
+        let cursor_position = q_windows
+            .get(widget.window)
+            .ok()
+            .and_then(ui_cursor_position);
+
+        if let Some(cursor_position) = cursor_position {
+            if cursor_position.distance(widget.absolute_position) > settings.toggle_threshold {
+                widget.moved = true;
+            }
+        }
+    }
+}
+
+pub fn scroll_indicator_toggled_subscriber(
+    mut er_scroll_content_indicator_toggled: EventReader<UiEvent<ScrollIndicatorWidget, ScrollIndicatorToggled>>,
+    mut q_scroll_indicators: Query<&mut ScrollIndicatorWidget>,
+) {
+    for event in er_scroll_content_indicator_toggled.iter() {
+        if let Ok(mut widget) = q_scroll_indicators.get_mut(event.widget) {
+            widget.mode = ScrollIndicatorMode::Toggled;
         }
     }
 }
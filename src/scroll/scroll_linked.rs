@@ -0,0 +1,406 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+};
+
+use super::components::*;
+use super::scroll_container::ScrollContainerElement;
+use super::scroll_content::{offset_scroll_content, ScrollContentElement, ScrollContentWidget};
+use super::{run_ui_propagate_schedule, PropagateSchedule, ScrollSystemSet};
+
+// Drives a property of the entity it's on from the offset of the `source` scroll container,
+// for parallax layers, fading headers or progress bars.
+#[derive(Component, Clone, Debug, Reflect, FromReflect)]
+#[reflect(Component, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollLinked {
+    pub source: Entity,
+    pub axis: ScrollAxis,
+    pub input: ScrollLinkInput,
+    pub curve: ScrollLinkCurve,
+    pub target: ScrollLinkTarget,
+}
+
+// Only used by reflection, a link without a source does nothing.
+impl FromWorld for ScrollLinked {
+    fn from_world(_world: &mut World) -> Self {
+        ScrollLinked {
+            source: Entity::PLACEHOLDER,
+            axis: ScrollAxis::Vertical,
+            input: ScrollLinkInput::default(),
+            curve: ScrollLinkCurve::default(),
+            target: ScrollLinkTarget::Alpha,
+        }
+    }
+}
+
+// A source outside the loaded scene keeps its entity, so scenes can link to containers that
+// are already spawned.
+impl MapEntities for ScrollLinked {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        if let Ok(mapped_source) = entity_map.get(self.source) {
+            self.source = mapped_source;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollLinkInput {
+    // The offset from 0 at the start to 1 at the end, or 0 when the axis can't scroll.
+    #[default]
+    Normalized,
+    // The offset in pixels.
+    Pixels,
+}
+
+// Maps `input_range` onto `output_range` through `easing`. Unclamped curves carry on linearly
+// past either end of the input range.
+#[derive(Copy, Clone, PartialEq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollLinkCurve {
+    pub input_range: Vec2,
+    pub output_range: Vec2,
+    pub easing: ScrollEasing,
+    pub clamp: bool,
+}
+
+impl Default for ScrollLinkCurve {
+    fn default() -> Self {
+        ScrollLinkCurve {
+            input_range: Vec2::new(0.0, 1.0),
+            output_range: Vec2::new(0.0, 1.0),
+            easing: ScrollEasing::Linear,
+            clamp: true,
+        }
+    }
+}
+
+impl ScrollLinkCurve {
+    pub fn linear(input_range: Vec2, output_range: Vec2) -> Self {
+        ScrollLinkCurve {
+            input_range,
+            output_range,
+            ..default()
+        }
+    }
+
+    // Scales the input, e.g. a parallax layer moving at a fraction of a pixel offset.
+    pub fn factor(factor: f32) -> Self {
+        ScrollLinkCurve {
+            output_range: Vec2::new(0.0, factor),
+            clamp: false,
+            ..default()
+        }
+    }
+
+    pub fn sample(&self, input: f32) -> f32 {
+        let input_length = self.input_range.y - self.input_range.x;
+        let progress = match input_length != 0.0 {
+            true => (input - self.input_range.x) / input_length,
+            false => 1.0,
+        };
+
+        let eased = match self.clamp {
+            true => self.easing.ease(progress.clamp(0.0, 1.0)),
+            false if (0.0..=1.0).contains(&progress) => self.easing.ease(progress),
+            false => progress,
+        };
+
+        self.output_range.x + (self.output_range.y - self.output_range.x) * eased
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub enum ScrollLinkTarget {
+    // One side of `Style::position`, in logical pixels.
+    Left,
+    Top,
+    Right,
+    Bottom,
+    // Moves a UI node from where layout put it, or sets the translation of anything else.
+    TranslationX,
+    TranslationY,
+    // Radians around the z axis.
+    Rotation,
+    Scale,
+    // Of the background colour, which also tints images, and of any text.
+    Alpha,
+}
+
+impl ScrollLinkTarget {
+    pub fn is_translation(&self) -> bool {
+        matches!(self, ScrollLinkTarget::TranslationX | ScrollLinkTarget::TranslationY)
+    }
+}
+
+pub struct ScrollLinkedPlugin;
+
+impl Plugin for ScrollLinkedPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollLinked>()
+            .register_type::<ScrollLinkInput>()
+            .register_type::<ScrollLinkCurve>()
+            .register_type::<ScrollLinkTarget>()
+            .add_system(update_scroll_linked.in_set(ScrollSystemSet::Propagate))
+            .add_system(
+                update_scroll_linked
+                    .in_set(ScrollSystemSet::Propagate)
+                    .in_schedule(PropagateSchedule),
+            )
+            .add_system(
+                offset_scroll_linked
+                    .in_set(ScrollSystemSet::PostLayout)
+                    .after(run_ui_propagate_schedule)
+                    .after(offset_scroll_content),
+            );
+    }
+}
+
+// The source's offset once `constrain_scroll_content` has clamped it, put through the curve.
+fn scroll_linked_value(
+    link: &ScrollLinked,
+    q_container_elements: &Query<&ScrollContainerElement>,
+    q_content_widgets: &Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    q_nodes: &Query<&Node>,
+) -> Option<f32> {
+    let container_elem = q_container_elements.get(link.source).ok()?;
+    let (widget, elem) = q_content_widgets.get(container_elem.scroll_content_widget).ok()?;
+
+    let offset = match link.axis {
+        ScrollAxis::Horizontal => widget.current_offset.x,
+        ScrollAxis::Vertical => widget.current_offset.y,
+    };
+
+    let input = match link.input {
+        ScrollLinkInput::Pixels => offset,
+        ScrollLinkInput::Normalized => {
+            let content_size = q_nodes.get(elem.scroll_content).ok()?.size();
+            let wrapper_size = q_nodes.get(elem.scroll_wrapper).ok()?.size();
+            let max_offset = match link.axis {
+                ScrollAxis::Horizontal => content_size.x - wrapper_size.x,
+                ScrollAxis::Vertical => content_size.y - wrapper_size.y,
+            };
+
+            match max_offset > 0.0 {
+                true => (offset / max_offset).clamp(0.0, 1.0),
+                false => 0.0,
+            }
+        }
+    };
+
+    Some(link.curve.sample(input))
+}
+
+pub fn update_scroll_linked(
+    mut q_linked: Query<(
+        &ScrollLinked,
+        Option<&mut Style>,
+        Option<&mut BackgroundColor>,
+        Option<&mut Text>,
+        Option<&mut Transform>,
+    )>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    q_nodes: Query<&Node>,
+) {
+    for (link, style, background_color, text, transform) in q_linked.iter_mut() {
+        if link.target.is_translation() {
+            continue;
+        }
+
+        let Some(value) =
+            scroll_linked_value(link, &q_container_elements, &q_content_widgets, &q_nodes)
+        else {
+            continue;
+        };
+
+        match link.target {
+            ScrollLinkTarget::Left
+            | ScrollLinkTarget::Top
+            | ScrollLinkTarget::Right
+            | ScrollLinkTarget::Bottom => {
+                let Some(mut style) = style else {
+                    continue;
+                };
+
+                let position = Val::Px(value);
+                let side = match link.target {
+                    ScrollLinkTarget::Left => style.position.left,
+                    ScrollLinkTarget::Top => style.position.top,
+                    ScrollLinkTarget::Right => style.position.right,
+                    _ => style.position.bottom,
+                };
+                if side == position {
+                    continue;
+                }
+
+                match link.target {
+                    ScrollLinkTarget::Left => style.position.left = position,
+                    ScrollLinkTarget::Top => style.position.top = position,
+                    ScrollLinkTarget::Right => style.position.right = position,
+                    _ => style.position.bottom = position,
+                }
+            }
+            ScrollLinkTarget::Rotation => {
+                let Some(mut transform) = transform else {
+                    continue;
+                };
+
+                let rotation = Quat::from_rotation_z(value);
+                if transform.rotation != rotation {
+                    transform.rotation = rotation;
+                }
+            }
+            ScrollLinkTarget::Scale => {
+                let Some(mut transform) = transform else {
+                    continue;
+                };
+
+                let scale = Vec3::new(value, value, transform.scale.z);
+                if transform.scale != scale {
+                    transform.scale = scale;
+                }
+            }
+            ScrollLinkTarget::Alpha => {
+                let alpha = value.clamp(0.0, 1.0);
+
+                if let Some(mut background_color) = background_color {
+                    if background_color.0.a() != alpha {
+                        background_color.0.set_a(alpha);
+                    }
+                }
+
+                if let Some(mut text) = text {
+                    if text.sections.iter().any(|section| section.style.color.a() != alpha) {
+                        for section in text.sections.iter_mut() {
+                            section.style.color.set_a(alpha);
+                        }
+                    }
+                }
+            }
+            ScrollLinkTarget::TranslationX | ScrollLinkTarget::TranslationY => {}
+        }
+    }
+}
+
+// Layout rewrites UI nodes' translation every frame, so like the content offset the link's is
+// added on top of it afterwards.
+pub fn offset_scroll_linked(
+    mut q_linked: Query<(&ScrollLinked, &mut Transform, Option<&Node>)>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_content_widgets: Query<(&ScrollContentWidget, &ScrollContentElement)>,
+    q_nodes: Query<&Node>,
+) {
+    for (link, mut transform, node) in q_linked.iter_mut() {
+        if !link.target.is_translation() {
+            continue;
+        }
+
+        let Some(value) =
+            scroll_linked_value(link, &q_container_elements, &q_content_widgets, &q_nodes)
+        else {
+            continue;
+        };
+
+        if node.is_some() {
+            let offset = match link.target {
+                ScrollLinkTarget::TranslationX => Vec3::new(value, 0.0, 0.0),
+                _ => Vec3::new(0.0, value, 0.0),
+            };
+            if offset != Vec3::ZERO {
+                transform.translation += offset;
+            }
+            continue;
+        }
+
+        let current = match link.target {
+            ScrollLinkTarget::TranslationX => transform.translation.x,
+            _ => transform.translation.y,
+        };
+        if current == value {
+            continue;
+        }
+
+        match link.target {
+            ScrollLinkTarget::TranslationX => transform.translation.x = value,
+            _ => transform.translation.y = value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASE_IN: ScrollEasing = ScrollEasing::CubicBezier {
+        p1: Vec2::new(0.42, 0.0),
+        p2: Vec2::new(1.0, 1.0),
+    };
+
+    #[test]
+    fn linear_curve_maps_input_range_onto_output_range() {
+        let curve = ScrollLinkCurve::linear(Vec2::new(100.0, 300.0), Vec2::new(1.0, 0.0));
+        assert_eq!(curve.sample(100.0), 1.0);
+        assert_eq!(curve.sample(200.0), 0.5);
+        assert_eq!(curve.sample(300.0), 0.0);
+    }
+
+    #[test]
+    fn clamped_curve_holds_its_ends_outside_the_input_range() {
+        let curve = ScrollLinkCurve::linear(Vec2::new(100.0, 300.0), Vec2::new(10.0, 20.0));
+        assert_eq!(curve.sample(0.0), 10.0);
+        assert_eq!(curve.sample(500.0), 20.0);
+    }
+
+    #[test]
+    fn unclamped_curve_eases_within_the_input_range() {
+        let clamped = ScrollLinkCurve {
+            easing: EASE_IN,
+            ..ScrollLinkCurve::linear(Vec2::new(0.0, 200.0), Vec2::new(0.0, 10.0))
+        };
+        let unclamped = ScrollLinkCurve {
+            clamp: false,
+            ..clamped
+        };
+
+        for input in [0.0, 50.0, 100.0, 150.0, 200.0] {
+            assert_eq!(unclamped.sample(input), clamped.sample(input));
+        }
+    }
+
+    #[test]
+    fn unclamped_curve_extrapolates_linearly_outside_the_input_range() {
+        let curve = ScrollLinkCurve {
+            easing: EASE_IN,
+            clamp: false,
+            ..ScrollLinkCurve::linear(Vec2::new(0.0, 200.0), Vec2::new(0.0, 10.0))
+        };
+        assert_eq!(curve.sample(-100.0), -5.0);
+        assert_eq!(curve.sample(400.0), 20.0);
+    }
+
+    #[test]
+    fn zero_length_input_range_samples_the_end_of_the_output_range() {
+        let curve = ScrollLinkCurve::linear(Vec2::new(50.0, 50.0), Vec2::new(2.0, 4.0));
+        assert_eq!(curve.sample(0.0), 4.0);
+        assert_eq!(curve.sample(50.0), 4.0);
+        assert_eq!(curve.sample(100.0), 4.0);
+    }
+
+    #[test]
+    fn factor_scales_the_input_without_clamping() {
+        let curve = ScrollLinkCurve::factor(0.5);
+        assert_eq!(curve.sample(0.0), 0.0);
+        assert_eq!(curve.sample(1.0), 0.5);
+        assert_eq!(curve.sample(240.0), 120.0);
+        assert_eq!(curve.sample(-40.0), -20.0);
+    }
+}
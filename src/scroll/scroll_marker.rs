@@ -0,0 +1,312 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use super::components::*;
+use super::scroll_bar::{ScrollBarElement, ScrollBarWidget};
+use super::scroll_container::{ScrollContainerElement, ScrollContainerWidget, ScrollMetrics};
+use super::styles::{scroll_marker_style, scroll_marker_tooltip_style};
+use super::ScrollSystemSet;
+
+use std::marker::PhantomData;
+
+// Ticks drawn on a container's bars at points of interest in its content, such as search hits.
+// Pressing a tick centres the view on its position.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ScrollMarkers {
+    pub markers: Vec<ScrollMarker>,
+    // Tick size along the bar, in logical pixels.
+    pub thickness: f32,
+    pub tooltip_text: TextStyle,
+    pub tooltip_background: Color,
+}
+
+impl Default for ScrollMarkers {
+    fn default() -> Self {
+        ScrollMarkers {
+            markers: Vec::new(),
+            thickness: 3.0,
+            tooltip_text: TextStyle {
+                font_size: 14.0,
+                color: Color::WHITE,
+                ..default()
+            },
+            tooltip_background: Color::rgba(0.0, 0.0, 0.0, 0.8),
+        }
+    }
+}
+
+impl ScrollMarkers {
+    pub fn with(markers: impl IntoIterator<Item = ScrollMarker>) -> Self {
+        ScrollMarkers {
+            markers: markers.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    pub fn push(&mut self, marker: ScrollMarker) {
+        self.markers.push(marker);
+    }
+}
+
+// `position` is in content pixels along `axis`, and the marker is drawn on the bar for it.
+#[derive(Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub struct ScrollMarker {
+    pub axis: ScrollAxis,
+    pub position: f32,
+    pub color: Color,
+    pub tooltip: Option<String>,
+}
+
+impl ScrollMarker {
+    pub fn new(axis: ScrollAxis, position: f32, color: Color) -> Self {
+        ScrollMarker {
+            axis,
+            position,
+            color,
+            tooltip: None,
+        }
+    }
+
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+}
+
+// The tick built for each marker, in the same order, and the bars they were built on.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ScrollMarkersElement {
+    pub ticks: Vec<Option<Entity>>,
+    pub bar_widgets: Vec<Entity>,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ScrollMarkerTick {
+    pub container: Entity,
+    pub index: usize,
+    pub tooltip: Option<Entity>,
+}
+
+pub struct ScrollMarkerPlugin;
+
+impl Plugin for ScrollMarkerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollMarkers>()
+            .register_type::<ScrollMarker>()
+            .register_type::<Vec<ScrollMarker>>()
+            .add_system(init_scroll_markers.in_set(ScrollSystemSet::Create))
+            .add_system(scroll_marker_press_publisher.in_set(ScrollSystemSet::Interact))
+            .add_system(extract_scroll_markers.in_set(ScrollSystemSet::Extract));
+    }
+}
+
+// Rebuilds a container's ticks whenever its markers change, or its bars have been rebuilt and
+// taken the ticks with them. Markers on an axis without a bar aren't drawn.
+pub fn init_scroll_markers(
+    q_containers: Query<(
+        Entity,
+        Ref<ScrollMarkers>,
+        &ScrollContainerElement,
+        Option<&ScrollMarkersElement>,
+    )>,
+    q_scroll_bar_widgets: Query<(&ScrollBarWidget, &ScrollBarElement)>,
+    mut removed_markers: RemovedComponents<ScrollMarkers>,
+    q_markers_elements: Query<&ScrollMarkersElement>,
+    q_ticks: Query<(), With<ScrollMarkerTick>>,
+    mut commands: Commands,
+) {
+    for container in removed_markers.iter() {
+        if let Ok(markers_elem) = q_markers_elements.get(container) {
+            for tick in markers_elem.ticks.iter().flatten() {
+                if let Some(tick) = commands.get_entity(*tick) {
+                    tick.despawn_recursive();
+                }
+            }
+            commands.entity(container).remove::<ScrollMarkersElement>();
+        }
+    }
+
+    for (container, markers, container_elem, markers_elem) in q_containers.iter() {
+        let ticks_intact = markers_elem.map_or(false, |markers_elem| {
+            markers_elem.bar_widgets == container_elem.scroll_bar_widgets
+                && markers_elem.ticks.iter().flatten().all(|tick| q_ticks.contains(*tick))
+        });
+        if ticks_intact && !markers.is_changed() {
+            continue;
+        }
+
+        // The bars are spawned a frame after their container.
+        let bars: Vec<(&ScrollBarWidget, &ScrollBarElement)> = container_elem
+            .scroll_bar_widgets
+            .iter()
+            .filter_map(|bar_widget| q_scroll_bar_widgets.get(*bar_widget).ok())
+            .collect();
+        if bars.len() != container_elem.scroll_bar_widgets.len() {
+            continue;
+        }
+
+        if let Some(markers_elem) = markers_elem {
+            for tick in markers_elem.ticks.iter().flatten() {
+                if let Some(tick) = commands.get_entity(*tick) {
+                    tick.despawn_recursive();
+                }
+            }
+        }
+
+        let ticks = markers
+            .markers
+            .iter()
+            .enumerate()
+            .map(|(index, marker)| {
+                let (bar_widget, bar_elem) = bars
+                    .iter()
+                    .find(|(bar_widget, _)| bar_widget.orientation.axis() == marker.axis)?;
+
+                let tooltip = marker.tooltip.as_ref().map(|tooltip| {
+                    let text_style = markers.tooltip_text.clone();
+                    let text = commands
+                        .spawn(TextBundle::from_section(tooltip.clone(), text_style))
+                        .id();
+
+                    let side = bar_widget.placement.side;
+                    commands
+                        .spawn(NodeBundle {
+                            style: scroll_marker_tooltip_style(marker.axis, side),
+                            background_color: BackgroundColor(markers.tooltip_background),
+                            focus_policy: FocusPolicy::Pass,
+                            ..default()
+                        })
+                        .add_child(text)
+                        .id()
+                });
+
+                // Blocking keeps the press from also reaching the bar, which would drag it.
+                let tick = commands
+                    .spawn(NodeBundle {
+                        style: scroll_marker_style(marker.axis, 0.0, markers.thickness),
+                        background_color: BackgroundColor(marker.color),
+                        focus_policy: FocusPolicy::Block,
+                        ..default()
+                    })
+                    .insert(Interaction::None)
+                    .insert(ScrollMarkerTick {
+                        container,
+                        index,
+                        tooltip,
+                    })
+                    .id();
+
+                if let Some(tooltip) = tooltip {
+                    commands.entity(tick).add_child(tooltip);
+                }
+
+                // Re-adding the handle keeps it last, drawn over the ticks.
+                commands
+                    .entity(bar_elem.scroll_bar)
+                    .push_children(&[tick, bar_elem.scroll_handle]);
+
+                Some(tick)
+            })
+            .collect();
+
+        commands.entity(container).insert(ScrollMarkersElement {
+            ticks,
+            bar_widgets: container_elem.scroll_bar_widgets.clone(),
+        });
+    }
+}
+
+pub fn scroll_marker_press_publisher(
+    q_ticks: Query<(&ScrollMarkerTick, &Interaction), Changed<Interaction>>,
+    q_containers: Query<(&ScrollMarkers, &ScrollMetrics)>,
+    mut ew_scroll_axis_to: EventWriter<UiEvent<ScrollContainerWidget, ScrollAxisTo>>,
+) {
+    for (tick, interaction) in q_ticks.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let Ok((markers, metrics)) = q_containers.get(tick.container) else {
+            continue;
+        };
+        let Some(marker) = markers.markers.get(tick.index) else {
+            continue;
+        };
+
+        let (viewport_length, max_offset) = match marker.axis {
+            ScrollAxis::Horizontal => (metrics.viewport_size.x, metrics.max_offset.x),
+            ScrollAxis::Vertical => (metrics.viewport_size.y, metrics.max_offset.y),
+        };
+
+        if max_offset <= 0.0 {
+            continue;
+        }
+
+        let centred_offset = marker.position - viewport_length / 2.0;
+
+        ew_scroll_axis_to.send(UiEvent {
+            widget: tick.container,
+            action: ScrollAxisTo {
+                axis: marker.axis,
+                normalized: (centred_offset / max_offset).clamp(0.0, 1.0),
+                source: ScrollSource::Programmatic,
+            },
+            element_kind: PhantomData::<ScrollContainerWidget>,
+        });
+    }
+}
+
+// Places each tick as a share of the content's length, so they follow the content as it
+// resizes, and shows the tooltip of the tick under the cursor.
+pub fn extract_scroll_markers(
+    q_ticks: Query<(Entity, &ScrollMarkerTick, &Interaction)>,
+    q_containers: Query<(&ScrollMarkers, &ScrollMetrics)>,
+    mut q_nodes: Query<(&mut Style, &mut BackgroundColor)>,
+) {
+    for (tick_entity, tick, interaction) in q_ticks.iter() {
+        let Ok((markers, metrics)) = q_containers.get(tick.container) else {
+            continue;
+        };
+        let Some(marker) = markers.markers.get(tick.index) else {
+            continue;
+        };
+
+        let content_length = match marker.axis {
+            ScrollAxis::Horizontal => metrics.content_size.x,
+            ScrollAxis::Vertical => metrics.content_size.y,
+        };
+        let fraction = match content_length > 0.0 {
+            true => (marker.position / content_length).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+
+        let (mut tick_style, mut tick_color) = q_nodes
+            .get_mut(tick_entity)
+            .expect("ScrollMarkerTick should have been a (Style, Node).");
+
+        let new_tick_style = scroll_marker_style(marker.axis, fraction, markers.thickness);
+        if *tick_style != new_tick_style {
+            *tick_style = new_tick_style;
+        }
+        if tick_color.0 != marker.color {
+            tick_color.0 = marker.color;
+        }
+
+        let Some(tooltip) = tick.tooltip else {
+            continue;
+        };
+        let Ok((mut tooltip_style, _)) = q_nodes.get_mut(tooltip) else {
+            continue;
+        };
+
+        let display = match interaction {
+            Interaction::None => Display::None,
+            Interaction::Hovered | Interaction::Clicked => Display::Flex,
+        };
+        if tooltip_style.display != display {
+            tooltip_style.display = display;
+        }
+    }
+}
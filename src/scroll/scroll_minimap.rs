@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use super::components::*;
+use super::scroll_bar::{ScrollBarElement, ScrollBarWidget};
+use super::scroll_container::ScrollContainerElement;
+use super::scroll_content::ScrollContentElement;
+use super::scroll_theme::ScrollStateColors;
+use super::styles::{scroll_minimap_block_style, scroll_minimap_style};
+use super::ScrollSystemSet;
+
+// Turns a bar into a thumbnail of the content, with the handle marking the visible part. Goes
+// on a bar widget, or on a container to apply to its bar of the same orientation.
+//
+// The content is scaled so its length fills the bar, which is exactly where a proportional
+// handle sits, so the handle is sized that way while the minimap is on, whatever the bar's own
+// sizing, and pressing or dragging works as usual.
+#[derive(Component, Clone, Debug, PartialEq, Reflect, FromReflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollMinimap {
+    pub orientation: ScrollBarOrientation,
+    // Drawn for text nodes; other nodes are drawn in their background colour, or not at all
+    // when it's transparent.
+    pub text_color: Color,
+    // Replaces the theme's handle colours so the blocks show through.
+    pub viewport_color: ScrollStateColors,
+    // Nodes smaller than this in the minimap, in logical pixels, are left out.
+    pub min_block_size: f32,
+    pub max_blocks: usize,
+}
+
+impl Default for ScrollMinimap {
+    fn default() -> Self {
+        ScrollMinimap {
+            orientation: ScrollBarOrientation::Vertical,
+            text_color: Color::rgb(0.7, 0.7, 0.7),
+            viewport_color: ScrollStateColors {
+                idle: Color::rgba(1.0, 1.0, 1.0, 0.15),
+                hovered: Color::rgba(1.0, 1.0, 1.0, 0.2),
+                pressed: Color::rgba(1.0, 1.0, 1.0, 0.3),
+                disabled: Color::NONE,
+            },
+            min_block_size: 0.5,
+            max_blocks: 512,
+        }
+    }
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct ScrollMinimapElement {
+    pub minimap: ScrollMinimap,
+    pub layer: Entity,
+    // Reused from frame to frame, the ones not needed are hidden.
+    pub blocks: Vec<Entity>,
+    // Set until the blocks have been drawn for the first time.
+    pub needs_redraw: bool,
+}
+
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ScrollMinimapBlock;
+
+pub struct ScrollMinimapPlugin;
+
+impl Plugin for ScrollMinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollMinimap>()
+            .add_system(init_scroll_minimap.in_set(ScrollSystemSet::Create))
+            .add_system(extract_scroll_minimap.in_set(ScrollSystemSet::Extract));
+    }
+}
+
+pub fn init_scroll_minimap(
+    q_scroll_bar_widgets: Query<(
+        Entity,
+        &ScrollBarWidget,
+        &ScrollBarElement,
+        Option<&ScrollMinimapElement>,
+    )>,
+    q_minimaps: Query<&ScrollMinimap>,
+    q_parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for (widget_entity, widget, elem, minimap_elem) in q_scroll_bar_widgets.iter() {
+        let minimap = widget_or_parent(widget_entity, &q_minimaps, &q_parents)
+            .filter(|minimap| minimap.orientation == widget.orientation);
+        if minimap_elem.map(|minimap_elem| &minimap_elem.minimap) == minimap {
+            continue;
+        }
+
+        if let Some(minimap_elem) = minimap_elem {
+            commands.entity(minimap_elem.layer).despawn_recursive();
+        }
+
+        let Some(minimap) = minimap else {
+            commands.entity(widget_entity).remove::<ScrollMinimapElement>();
+            continue;
+        };
+
+        let layer = commands
+            .spawn(NodeBundle {
+                style: scroll_minimap_style(),
+                focus_policy: FocusPolicy::Pass,
+                ..default()
+            })
+            .id();
+
+        commands.entity(elem.scroll_bar).insert_children(0, &[layer]);
+
+        commands.entity(widget_entity).insert(ScrollMinimapElement {
+            minimap: minimap.clone(),
+            layer,
+            blocks: Vec::new(),
+            needs_redraw: true,
+        });
+    }
+}
+
+// Redraws the minimap from the content's nodes as last laid out, when any of them has been
+// resized, moved, recoloured, added or removed, or the bar has been resized. Scrolling only
+// moves the content node itself, which leaves the minimap as it is.
+pub fn extract_scroll_minimap(
+    mut q_scroll_bar_widgets: Query<(
+        &ScrollBarWidget,
+        &ScrollBarElement,
+        &mut ScrollMinimapElement,
+        &Parent,
+    )>,
+    q_container_elements: Query<&ScrollContainerElement>,
+    q_content_elements: Query<&ScrollContentElement>,
+    q_changed_nodes: Query<
+        Entity,
+        (
+            Without<ScrollMinimapBlock>,
+            Or<(
+                Changed<Node>,
+                Changed<Children>,
+                Changed<BackgroundColor>,
+                Changed<Text>,
+            )>,
+        ),
+    >,
+    q_moved_nodes: Query<Entity, (With<Node>, Without<ScrollMinimapBlock>, Changed<Transform>)>,
+    mut removed_children: RemovedComponents<Children>,
+    q_parents: Query<&Parent>,
+    q_content_nodes: Query<
+        (
+            &Node,
+            &GlobalTransform,
+            Option<&BackgroundColor>,
+            Option<&Text>,
+            Option<&Children>,
+        ),
+        Without<ScrollMinimapBlock>,
+    >,
+    mut q_blocks: Query<(&mut Style, &mut BackgroundColor), With<ScrollMinimapBlock>>,
+    q_nodes: Query<Ref<Node>>,
+    ui_scale: Res<UiScale>,
+    mut commands: Commands,
+) {
+    let scroll_content = |container: &Parent| {
+        let container_elem = q_container_elements.get(container.get()).ok()?;
+        let content_elem = q_content_elements.get(container_elem.scroll_content_widget).ok()?;
+        Some(content_elem.scroll_content)
+    };
+
+    let minimap_contents: HashSet<Entity> = q_scroll_bar_widgets
+        .iter()
+        .filter_map(|(.., container)| scroll_content(container))
+        .collect();
+
+    // The content nodes with a minimap that one of their own nodes has changed in.
+    let mut changed_contents = HashSet::new();
+    let mut mark_changed = |entity: Entity| {
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            if minimap_contents.contains(&entity) {
+                changed_contents.insert(entity);
+                return;
+            }
+            current = q_parents.get(entity).ok().map(|parent| parent.get());
+        }
+    };
+
+    for entity in q_changed_nodes.iter() {
+        mark_changed(entity);
+    }
+    for entity in q_moved_nodes.iter() {
+        if !minimap_contents.contains(&entity) {
+            mark_changed(entity);
+        }
+    }
+    // A node that lost its last child has no `Children` left to be changed.
+    for entity in removed_children.iter() {
+        mark_changed(entity);
+    }
+
+    let ui_scale_changed = ui_scale.is_changed();
+    let ui_scale = ui_scale.scale as f32;
+
+    for (widget, elem, mut minimap_elem, container) in q_scroll_bar_widgets.iter_mut() {
+        let Some(scroll_content) = scroll_content(container) else {
+            continue;
+        };
+        let (Ok(bar_node), Ok((content_node, content_transform, ..))) =
+            (q_nodes.get(elem.scroll_bar), q_content_nodes.get(scroll_content))
+        else {
+            continue;
+        };
+
+        if !minimap_elem.needs_redraw
+            && !ui_scale_changed
+            && !bar_node.is_changed()
+            && !changed_contents.contains(&scroll_content)
+        {
+            continue;
+        }
+        minimap_elem.needs_redraw = false;
+
+        let content_size = content_node.size();
+        let content_origin = content_transform.translation().truncate() - content_size / 2.0;
+
+        // Along the bar the content fills its length, across it keeps its aspect unless that
+        // would overflow the bar's girth.
+        let bar_size = bar_node.size();
+        let scale = match widget.orientation {
+            ScrollBarOrientation::Vertical => {
+                let along = bar_size.y / content_size.y.max(1.0);
+                Vec2::new(along.min(bar_size.x / content_size.x.max(1.0)), along)
+            }
+            ScrollBarOrientation::Horizontal => {
+                let along = bar_size.x / content_size.x.max(1.0);
+                Vec2::new(along, along.min(bar_size.y / content_size.y.max(1.0)))
+            }
+        };
+
+        let minimap = &minimap_elem.minimap;
+        let mut blocks = Vec::new();
+        let mut stack: Vec<Entity> = q_content_nodes
+            .get(scroll_content)
+            .ok()
+            .and_then(|(.., children)| children)
+            .map(|children| children.iter().rev().copied().collect())
+            .unwrap_or_default();
+
+        while let Some(node_entity) = stack.pop() {
+            if blocks.len() >= minimap.max_blocks {
+                break;
+            }
+
+            let Ok((node, transform, background_color, text, children)) =
+                q_content_nodes.get(node_entity)
+            else {
+                continue;
+            };
+
+            if let Some(children) = children {
+                stack.extend(children.iter().rev());
+            }
+
+            let color = match (text, background_color) {
+                (Some(_), _) => minimap.text_color,
+                (None, Some(BackgroundColor(color))) if color.a() > 0.0 => *color,
+                _ => continue,
+            };
+
+            let node_origin = transform.translation().truncate() - node.size() / 2.0;
+            let min = (node_origin - content_origin) * scale;
+            let rect = Rect::from_corners(min, min + node.size() * scale);
+            if rect.width().max(rect.height()) * ui_scale < minimap.min_block_size {
+                continue;
+            }
+
+            blocks.push((
+                Rect::from_corners(rect.min / ui_scale, rect.max / ui_scale),
+                color,
+            ));
+        }
+
+        for (index, (rect, color)) in blocks.iter().enumerate() {
+            let style = scroll_minimap_block_style(*rect);
+
+            let Some(block) = minimap_elem.blocks.get(index) else {
+                let block = commands
+                    .spawn(NodeBundle {
+                        style,
+                        background_color: BackgroundColor(*color),
+                        focus_policy: FocusPolicy::Pass,
+                        ..default()
+                    })
+                    .insert(ScrollMinimapBlock)
+                    .id();
+                commands.entity(minimap_elem.layer).add_child(block);
+                minimap_elem.blocks.push(block);
+                continue;
+            };
+
+            if let Ok((mut block_style, mut block_color)) = q_blocks.get_mut(*block) {
+                if *block_style != style {
+                    *block_style = style;
+                }
+                if block_color.0 != *color {
+                    block_color.0 = *color;
+                }
+            }
+        }
+
+        for block in minimap_elem.blocks.iter().skip(blocks.len()) {
+            if let Ok((mut block_style, _)) = q_blocks.get_mut(*block) {
+                if block_style.display != Display::None {
+                    block_style.display = Display::None;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use bevy::{prelude::*, utils};
+
+use super::components::*;
+use super::scroll_container::{
+    scroll_container_scroll_to_subscriber, update_scroll_metrics, ScrollContainerWidget,
+    ScrollMetrics,
+};
+use super::ScrollSystemSet;
+
+// Containers with the same key share a saved position, so a menu that is despawned and
+// later respawned opens where the player left it.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollPersistenceKey(pub String);
+
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ScrollPositionRestored;
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct SavedScrollPosition {
+    pub offset: Vec2,
+    pub normalized: Vec2,
+}
+
+#[derive(Resource, Clone, Debug, Default, Reflect, FromReflect)]
+#[reflect(Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollPersistence {
+    pub positions: utils::HashMap<String, SavedScrollPosition>,
+}
+
+pub struct ScrollPersistencePlugin;
+
+impl Plugin for ScrollPersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollPersistenceKey>()
+            .register_type::<SavedScrollPosition>()
+            .register_type::<utils::HashMap<String, SavedScrollPosition>>()
+            .register_type::<ScrollPersistence>()
+            .init_resource::<ScrollPersistence>()
+            .add_system(
+                restore_scroll_positions
+                    .in_set(ScrollSystemSet::Update)
+                    .before(scroll_container_scroll_to_subscriber),
+            )
+            .add_system(
+                record_scroll_positions
+                    .in_set(ScrollSystemSet::Extract)
+                    .after(update_scroll_metrics),
+            );
+    }
+}
+
+// Offsets within this many pixels of the current maximum are restored as pixels rather than
+// as a fraction, so small layout differences don't shift the view.
+const PIXEL_RESTORE_TOLERANCE: f32 = 0.5;
+
+pub fn restore_scroll_positions(
+    q_pending_containers: Query<
+        (Entity, &ScrollPersistenceKey, &ScrollMetrics),
+        (With<ScrollContainerWidget>, Without<ScrollPositionRestored>),
+    >,
+    persistence: Res<ScrollPersistence>,
+    mut ew_scroll_to: EventWriter<UiEvent<ScrollContainerWidget, ScrollTo>>,
+    mut commands: Commands,
+) {
+    for (entity, key, metrics) in q_pending_containers.iter() {
+        // Until layout has sized both nodes, clamping would throw the restored offset away.
+        if metrics.viewport_size.cmple(Vec2::ZERO).any()
+            || metrics.content_size.cmple(Vec2::ZERO).any()
+        {
+            continue;
+        }
+
+        if let Some(saved) = persistence.positions.get(&key.0) {
+            let fits = saved
+                .offset
+                .cmple(metrics.max_offset + PIXEL_RESTORE_TOLERANCE)
+                .all();
+
+            ew_scroll_to.send(UiEvent {
+                widget: entity,
+                action: if fits {
+                    ScrollTo::Offset(saved.offset)
+                } else {
+                    ScrollTo::Normalized(saved.normalized)
+                },
+                element_kind: PhantomData::<ScrollContainerWidget>,
+            });
+        }
+
+        commands.entity(entity).insert(ScrollPositionRestored);
+    }
+}
+
+// Positions are kept per entity while the container lives and saved under its key once the
+// key is removed, which despawning does too.
+pub fn record_scroll_positions(
+    q_restored_containers: Query<
+        (Entity, &ScrollPersistenceKey, &ScrollMetrics),
+        (
+            With<ScrollPositionRestored>,
+            Or<(
+                Changed<ScrollMetrics>,
+                Changed<ScrollPersistenceKey>,
+                Added<ScrollPositionRestored>,
+            )>,
+        ),
+    >,
+    mut removed_keys: RemovedComponents<ScrollPersistenceKey>,
+    mut live_positions: Local<HashMap<Entity, (String, SavedScrollPosition)>>,
+    mut persistence: ResMut<ScrollPersistence>,
+) {
+    for (entity, key, metrics) in q_restored_containers.iter() {
+        live_positions.insert(
+            entity,
+            (
+                key.0.clone(),
+                SavedScrollPosition {
+                    offset: metrics.offset,
+                    normalized: metrics.normalized,
+                },
+            ),
+        );
+    }
+
+    for entity in removed_keys.iter() {
+        if let Some((key, saved)) = live_positions.remove(&entity) {
+            persistence.positions.insert(key, saved);
+        }
+    }
+}
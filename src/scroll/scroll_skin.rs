@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use super::components::*;
+use super::scroll_bar::{ScrollBarElement, ScrollBarState};
+use super::scroll_theme::ScrollStateColors;
+use super::styles::{scroll_slice_cell_style, scroll_slice_image_style, scroll_slice_root_style};
+use super::ScrollSystemSet;
+
+// Image skins for scroll bars, on a bar widget or on its container to skin all of its bars.
+// Skinned parts are drawn with their skin's tints instead of the theme's colours.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect, FromReflect)]
+#[reflect(Component)]
+pub struct ScrollBarSkin {
+    pub track: Option<ScrollNineSlice>,
+    pub handle: Option<ScrollNineSlice>,
+    // Drawn in the middle of the handle.
+    pub grip: Option<ScrollGrip>,
+}
+
+// An image split by `border` into corners that keep their size, edges that stretch along one
+// axis and a centre that stretches along both.
+#[derive(Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub struct ScrollNineSlice {
+    pub image: Handle<Image>,
+    pub border: ScrollSliceBorder,
+    pub tint: ScrollStateColors,
+}
+
+impl ScrollNineSlice {
+    pub fn new(image: Handle<Image>, border: ScrollSliceBorder) -> Self {
+        ScrollNineSlice {
+            image,
+            border,
+            tint: ScrollStateColors::uniform(Color::WHITE),
+        }
+    }
+}
+
+// Border widths in image pixels, which are drawn as logical pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, FromReflect)]
+pub struct ScrollSliceBorder {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl ScrollSliceBorder {
+    pub fn all(border: f32) -> Self {
+        ScrollSliceBorder {
+            left: border,
+            right: border,
+            top: border,
+            bottom: border,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Reflect, FromReflect)]
+pub struct ScrollGrip {
+    pub image: Handle<Image>,
+    pub size: Vec2,
+    pub tint: ScrollStateColors,
+}
+
+impl ScrollGrip {
+    pub fn new(image: Handle<Image>, size: Vec2) -> Self {
+        ScrollGrip {
+            image,
+            size,
+            tint: ScrollStateColors::uniform(Color::WHITE),
+        }
+    }
+}
+
+// The nodes built for the skin a bar resolved to.
+#[derive(Component, Clone, Debug)]
+pub struct ScrollBarSkinElement {
+    pub skin: ScrollBarSkin,
+    pub track_slices: Option<ScrollSliceNodes>,
+    pub handle_slices: Option<ScrollSliceNodes>,
+    pub grip: Option<Entity>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ScrollSliceNodes {
+    pub root: Entity,
+    pub images: [Entity; 9],
+}
+
+pub struct ScrollSkinPlugin;
+
+impl Plugin for ScrollSkinPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollBarSkin>()
+            .register_type::<ScrollNineSlice>()
+            .register_type::<Option<ScrollNineSlice>>()
+            .register_type::<ScrollSliceBorder>()
+            .register_type::<ScrollGrip>()
+            .register_type::<Option<ScrollGrip>>()
+            .add_system(init_scroll_bar_skin.in_set(ScrollSystemSet::Create))
+            .add_system(extract_scroll_bar_skin.in_set(ScrollSystemSet::Extract));
+    }
+}
+
+// Rebuilds a bar's skin nodes whenever the skin it resolves to changes. The slices are placed
+// from the image sizes, so building waits until the images have loaded.
+pub fn init_scroll_bar_skin(
+    q_scroll_bar_widgets: Query<(Entity, &ScrollBarElement, Option<&ScrollBarSkinElement>)>,
+    q_skins: Query<&ScrollBarSkin>,
+    q_parents: Query<&Parent>,
+    images: Res<Assets<Image>>,
+    mut commands: Commands,
+) {
+    for (widget_entity, elem, skin_elem) in q_scroll_bar_widgets.iter() {
+        let skin = widget_or_parent(widget_entity, &q_skins, &q_parents);
+        if skin_elem.map(|skin_elem| &skin_elem.skin) == skin {
+            continue;
+        }
+
+        let image_size = |slice: &ScrollNineSlice| images.get(&slice.image).map(|image| image.size());
+        let track_size = skin.and_then(|skin| skin.track.as_ref()).map(image_size);
+        let handle_size = skin.and_then(|skin| skin.handle.as_ref()).map(image_size);
+        if track_size == Some(None) || handle_size == Some(None) {
+            continue;
+        }
+
+        if let Some(skin_elem) = skin_elem {
+            let slices = skin_elem.track_slices.iter().chain(skin_elem.handle_slices.iter());
+            for slice_nodes in slices {
+                commands.entity(slice_nodes.root).despawn_recursive();
+            }
+            if let Some(grip) = skin_elem.grip {
+                commands.entity(grip).despawn_recursive();
+            }
+        }
+
+        let Some(skin) = skin else {
+            commands.entity(widget_entity).remove::<ScrollBarSkinElement>();
+            continue;
+        };
+
+        // Slices go first among the part's children so they're drawn beneath the handle and grip.
+        let track_slices = skin.track.as_ref().zip(track_size.flatten()).map(|(slice, size)| {
+            let slice_nodes = spawn_nine_slice(&mut commands, slice, size);
+            commands
+                .entity(elem.scroll_bar)
+                .insert_children(0, &[slice_nodes.root]);
+            slice_nodes
+        });
+
+        let handle_slices = skin.handle.as_ref().zip(handle_size.flatten()).map(|(slice, size)| {
+            let slice_nodes = spawn_nine_slice(&mut commands, slice, size);
+            commands
+                .entity(elem.scroll_handle)
+                .insert_children(0, &[slice_nodes.root]);
+            slice_nodes
+        });
+
+        let grip = skin.grip.as_ref().map(|grip| {
+            let grip_entity = commands
+                .spawn(ImageBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(grip.size.x), Val::Px(grip.size.y)),
+                        flex_shrink: 0.0,
+                        ..default()
+                    },
+                    image: UiImage::new(grip.image.clone()),
+                    background_color: BackgroundColor(grip.tint.idle),
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .id();
+            commands.entity(elem.scroll_handle).add_child(grip_entity);
+            grip_entity
+        });
+
+        commands.entity(widget_entity).insert(ScrollBarSkinElement {
+            skin: skin.clone(),
+            track_slices,
+            handle_slices,
+            grip,
+        });
+    }
+}
+
+fn spawn_nine_slice(
+    commands: &mut Commands,
+    slice: &ScrollNineSlice,
+    image_size: Vec2,
+) -> ScrollSliceNodes {
+    let root = commands
+        .spawn(NodeBundle {
+            style: scroll_slice_root_style(),
+            focus_policy: FocusPolicy::Pass,
+            ..default()
+        })
+        .id();
+
+    let mut images = [Entity::PLACEHOLDER; 9];
+    for row in 0..3 {
+        for column in 0..3 {
+            let image = commands
+                .spawn(ImageBundle {
+                    style: scroll_slice_image_style(column, row, slice.border, image_size),
+                    image: UiImage::new(slice.image.clone()),
+                    background_color: BackgroundColor(slice.tint.idle),
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .id();
+
+            let cell = commands
+                .spawn(NodeBundle {
+                    style: scroll_slice_cell_style(column, row, slice.border),
+                    focus_policy: FocusPolicy::Pass,
+                    ..default()
+                })
+                .add_child(image)
+                .id();
+
+            commands.entity(root).add_child(cell);
+            images[row * 3 + column] = image;
+        }
+    }
+
+    ScrollSliceNodes { root, images }
+}
+
+pub fn extract_scroll_bar_skin(
+    q_scroll_bar_widgets: Query<(&ScrollBarSkinElement, &ScrollBarState)>,
+    mut q_colors: Query<&mut BackgroundColor>,
+) {
+    let mut set_color = |node: Entity, color: Color| {
+        if let Ok(mut background_color) = q_colors.get_mut(node) {
+            if background_color.0 != color {
+                background_color.0 = color;
+            }
+        }
+    };
+
+    for (skin_elem, state) in q_scroll_bar_widgets.iter() {
+        let skin = &skin_elem.skin;
+
+        if let (Some(slice), Some(slice_nodes)) = (&skin.track, &skin_elem.track_slices) {
+            for image in slice_nodes.images {
+                set_color(image, slice.tint.get(state.track));
+            }
+        }
+
+        if let (Some(slice), Some(slice_nodes)) = (&skin.handle, &skin_elem.handle_slices) {
+            for image in slice_nodes.images {
+                set_color(image, slice.tint.get(state.handle));
+            }
+        }
+
+        if let (Some(grip), Some(grip_entity)) = (&skin.grip, skin_elem.grip) {
+            set_color(grip_entity, grip.tint.get(state.handle));
+        }
+    }
+}
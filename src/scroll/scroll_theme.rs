@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use super::scroll_container::ScrollContainerElement;
+use super::scroll_content::ScrollContentElement;
+use super::scroll_indicator::ScrollIndicatorTextures;
+use super::ScrollSystemSet;
+
+// Colours and indicator images for scroll widgets. The resource applies to every container, a
+// component on a container or one of its widgets overrides it there. Changes to either are
+// reapplied to live widgets.
+#[derive(Resource, Component, Clone, Debug, Reflect, FromReflect)]
+#[reflect(Component, Resource)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollTheme {
+    pub track: ScrollStateColors,
+    pub handle: ScrollStateColors,
+    // Overrides the bars' handle girth per state, in logical pixels.
+    pub handle_girth: Option<ScrollStateSizes>,
+    // Shown while the cursor is over a handle or dragging it.
+    pub handle_cursor: Option<CursorIcon>,
+    pub content_background: Color,
+    pub wrapper_background: Color,
+    // Falls back to the global theme's images, then to `ScrollIndicatorTextures`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub indicator: Option<ScrollIndicatorTextures>,
+}
+
+impl Default for ScrollTheme {
+    fn default() -> Self {
+        ScrollTheme {
+            track: ScrollStateColors {
+                idle: Color::rgb(0.15, 0.15, 0.15),
+                hovered: Color::rgb(0.2, 0.2, 0.2),
+                pressed: Color::rgb(0.25, 0.25, 0.25),
+                disabled: Color::rgb(0.1, 0.1, 0.1),
+            },
+            handle: ScrollStateColors {
+                idle: Color::rgb(1.0, 1.0, 0.0),
+                hovered: Color::rgb(1.0, 1.0, 0.5),
+                pressed: Color::rgb(1.0, 0.8, 0.0),
+                disabled: Color::rgb(0.3, 0.3, 0.3),
+            },
+            handle_girth: None,
+            handle_cursor: None,
+            content_background: Color::rgb(0.25, 0.25, 0.25),
+            wrapper_background: Color::rgb(0.15, 0.15, 0.15),
+            indicator: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollStateColors {
+    pub idle: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+    pub disabled: Color,
+}
+
+impl ScrollStateColors {
+    pub fn uniform(color: Color) -> Self {
+        ScrollStateColors {
+            idle: color,
+            hovered: color,
+            pressed: color,
+            disabled: color,
+        }
+    }
+
+    pub fn get(&self, state: ScrollPartState) -> Color {
+        match state {
+            ScrollPartState::Idle => self.idle,
+            ScrollPartState::Hovered => self.hovered,
+            ScrollPartState::Pressed => self.pressed,
+            ScrollPartState::Disabled => self.disabled,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), reflect(Serialize, Deserialize))]
+pub struct ScrollStateSizes {
+    pub idle: f32,
+    pub hovered: f32,
+    pub pressed: f32,
+    pub disabled: f32,
+}
+
+impl ScrollStateSizes {
+    pub fn get(&self, state: ScrollPartState) -> f32 {
+        match state {
+            ScrollPartState::Idle => self.idle,
+            ScrollPartState::Hovered => self.hovered,
+            ScrollPartState::Pressed => self.pressed,
+            ScrollPartState::Disabled => self.disabled,
+        }
+    }
+}
+
+pub struct ScrollThemePlugin;
+
+impl Plugin for ScrollThemePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<ScrollTheme>()
+            .register_type::<ScrollStateColors>()
+            .register_type::<ScrollStateSizes>()
+            .register_type::<Option<ScrollStateSizes>>()
+            .register_type::<Option<CursorIcon>>()
+            .init_resource::<ScrollTheme>()
+            .add_system(apply_scroll_theme.in_set(ScrollSystemSet::Extract));
+    }
+}
+
+pub fn scroll_theme<'a>(
+    entity: Entity,
+    q_themes: &'a Query<&ScrollTheme>,
+    q_parents: &Query<&Parent>,
+    global_theme: &'a ScrollTheme,
+) -> &'a ScrollTheme {
+    widget_or_parent(entity, q_themes, q_parents).unwrap_or(global_theme)
+}
+
+// Bars pick their colours by state every frame in `extract_scroll_bar`, everything else is
+// only updated when a theme changes.
+pub fn apply_scroll_theme(
+    global_theme: Res<ScrollTheme>,
+    q_changed_themes: Query<(), Changed<ScrollTheme>>,
+    mut removed_themes: RemovedComponents<ScrollTheme>,
+    q_themes: Query<&ScrollTheme>,
+    q_parents: Query<&Parent>,
+    q_container_elements: Query<(Entity, &ScrollContainerElement)>,
+    q_content_elements: Query<&ScrollContentElement>,
+    mut q_colors: Query<&mut BackgroundColor>,
+) {
+    let themes_removed = removed_themes.iter().count() > 0;
+    if !global_theme.is_changed() && q_changed_themes.is_empty() && !themes_removed {
+        return;
+    }
+
+    let mut set_color = |node: Entity, color: Color| {
+        if let Ok(mut background_color) = q_colors.get_mut(node) {
+            if background_color.0 != color {
+                background_color.0 = color;
+            }
+        }
+    };
+
+    for (container, elem) in q_container_elements.iter() {
+        let container_theme = scroll_theme(container, &q_themes, &q_parents, &global_theme);
+        if let Some(scroll_corner) = elem.scroll_corner {
+            set_color(scroll_corner, container_theme.track.idle);
+        }
+
+        if let Ok(content_element) = q_content_elements.get(elem.scroll_content_widget) {
+            let theme =
+                scroll_theme(elem.scroll_content_widget, &q_themes, &q_parents, &global_theme);
+            set_color(content_element.scroll_content, theme.content_background);
+            set_color(content_element.scroll_wrapper, theme.wrapper_background);
+        }
+    }
+}
@@ -9,3 +9,21 @@ pub use super::styles::scroll_content::*;
 
 pub mod scroll_handle;
 pub use super::styles::scroll_handle::*;
+
+pub mod scroll_frame;
+pub use super::styles::scroll_frame::*;
+
+pub mod scroll_corner;
+pub use super::styles::scroll_corner::*;
+
+pub mod scroll_slice;
+pub use super::styles::scroll_slice::*;
+
+pub mod scroll_fade;
+pub use super::styles::scroll_fade::*;
+
+pub mod scroll_minimap;
+pub use super::styles::scroll_minimap::*;
+
+pub mod scroll_marker;
+pub use super::styles::scroll_marker::*;
@@ -1,41 +1,54 @@
 use bevy::prelude::*;
 use crate::scroll::components::*;
 
-pub fn scroll_bar_style(scroll_direction: ScrollBarOrientation, girth: Val) -> Style {
+// `insets` pulls the bar's ends in along its length, the side it sits on is always flush with
+// the frame.
+pub fn scroll_bar_style(
+    scroll_direction: ScrollBarOrientation,
+    placement: ScrollBarPlacement,
+    girth: Val,
+    insets: UiRect,
+) -> Style {
+    let mut position = UiRect::default();
+
     let size = match scroll_direction {
-        ScrollBarOrientation::Vertical => Size {
-            height: Val::Percent(90.0),
-            width: girth,
-            ..default()
-        },
-        ScrollBarOrientation::Horizontal => Size {
-            width: Val::Percent(90.0),
-            height: girth,
-            ..default()
-        },
-    };
+        ScrollBarOrientation::Vertical => {
+            position.top = insets.top;
+            position.bottom = insets.bottom;
+            match placement.side {
+                ScrollBarSide::Start => position.left = Val::Px(0.0),
+                ScrollBarSide::End => position.right = Val::Px(0.0),
+            }
 
-    let align_self = match scroll_direction {
-        ScrollBarOrientation::Vertical => AlignSelf::Center,
-        ScrollBarOrientation::Horizontal => AlignSelf::Auto
-    };
+            Size {
+                width: girth,
+                ..default()
+            }
+        }
+        ScrollBarOrientation::Horizontal => {
+            position.left = insets.left;
+            position.right = insets.right;
+            match placement.side {
+                ScrollBarSide::Start => position.top = Val::Px(0.0),
+                ScrollBarSide::End => position.bottom = Val::Px(0.0),
+            }
 
-    let margin_left = match scroll_direction {
-        ScrollBarOrientation::Vertical => Val::Auto,
-        ScrollBarOrientation::Horizontal => Val::Percent(5.0),
+            Size {
+                height: girth,
+                ..default()
+            }
+        }
     };
 
     return Style {
         position_type: PositionType::Absolute,
         size,
-        max_size: size,
-        position: UiRect { left: margin_left, ..default()},
+        position,
         flex_direction: match scroll_direction {
             ScrollBarOrientation::Vertical => FlexDirection::Column,
             ScrollBarOrientation::Horizontal => FlexDirection::Row,
         },
         align_items: AlignItems::Center,
-        align_self,
         ..default()
     };
-}
\ No newline at end of file
+}
@@ -14,6 +14,11 @@ pub fn scroll_content_style(scroll_direction: ScrollDirection) -> Style {
             ScrollDirection::Both => FlexDirection::Column,
             ScrollDirection::Neither => FlexDirection::DEFAULT,
         },
+        // Content that can't scroll is kept to the wrapper's size, its overflow clipped.
+        max_size: match scroll_direction {
+            ScrollDirection::Neither => Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+            _ => Size::AUTO,
+        },
         flex_grow: 1.0,
         flex_shrink: 0.0,
         overflow: Overflow::Hidden,
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use crate::scroll::components::*;
+
+pub fn scroll_corner_style(
+    vertical_bar: (ScrollBarSide, Val),
+    horizontal_bar: (ScrollBarSide, Val),
+) -> Style {
+    let (vertical_side, vertical_girth) = vertical_bar;
+    let (horizontal_side, horizontal_girth) = horizontal_bar;
+
+    let mut position = UiRect::default();
+    match vertical_side {
+        ScrollBarSide::Start => position.left = Val::Px(0.0),
+        ScrollBarSide::End => position.right = Val::Px(0.0),
+    }
+    match horizontal_side {
+        ScrollBarSide::Start => position.top = Val::Px(0.0),
+        ScrollBarSide::End => position.bottom = Val::Px(0.0),
+    }
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position,
+        size: Size {
+            width: vertical_girth,
+            height: horizontal_girth,
+        },
+        ..default()
+    };
+}
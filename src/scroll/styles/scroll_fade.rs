@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use crate::scroll::components::*;
+
+// A strip along one edge of the wrapper, its first child sitting against the edge.
+pub fn scroll_fade_style(axis: ScrollAxis, edge: ScrollEdge, size: Val) -> Style {
+    let mut position = UiRect::default();
+
+    let (size, flex_direction) = match axis {
+        ScrollAxis::Vertical => {
+            position.left = Val::Px(0.0);
+            position.right = Val::Px(0.0);
+            match edge {
+                ScrollEdge::Start => position.top = Val::Px(0.0),
+                ScrollEdge::End => position.bottom = Val::Px(0.0),
+            }
+
+            let flex_direction = match edge {
+                ScrollEdge::Start => FlexDirection::Column,
+                ScrollEdge::End => FlexDirection::ColumnReverse,
+            };
+            (Size { height: size, ..default() }, flex_direction)
+        }
+        ScrollAxis::Horizontal => {
+            position.top = Val::Px(0.0);
+            position.bottom = Val::Px(0.0);
+            match edge {
+                ScrollEdge::Start => position.left = Val::Px(0.0),
+                ScrollEdge::End => position.right = Val::Px(0.0),
+            }
+
+            let flex_direction = match edge {
+                ScrollEdge::Start => FlexDirection::Row,
+                ScrollEdge::End => FlexDirection::RowReverse,
+            };
+            (Size { width: size, ..default() }, flex_direction)
+        }
+    };
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position,
+        size,
+        flex_direction,
+        ..default()
+    };
+}
+
+pub fn scroll_fade_step_style() -> Style {
+    return Style {
+        flex_grow: 1.0,
+        flex_basis: Val::Px(0.0),
+        ..default()
+    };
+}
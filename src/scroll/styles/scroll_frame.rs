@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+pub fn scroll_frame_style(size: Size) -> Style {
+    return Style {
+        min_size: size,
+        size,
+        max_size: size,
+        ..default()
+    };
+}
@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 
+// Centres the optional grip image on the handle.
 pub fn scroll_handle_style() -> Style {
-    return Style::DEFAULT;
-}
\ No newline at end of file
+    return Style {
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..Style::DEFAULT
+    };
+}
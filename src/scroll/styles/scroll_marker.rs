@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use crate::scroll::components::*;
+
+// A tick across the bar, centred `fraction` of the way along it.
+pub fn scroll_marker_style(axis: ScrollAxis, fraction: f32, thickness: f32) -> Style {
+    let (position, size, margin) = match axis {
+        ScrollAxis::Vertical => (
+            UiRect {
+                left: Val::Px(0.0),
+                top: Val::Percent(fraction * 100.0),
+                ..default()
+            },
+            Size::new(Val::Percent(100.0), Val::Px(thickness)),
+            UiRect {
+                top: Val::Px(-thickness / 2.0),
+                ..default()
+            },
+        ),
+        ScrollAxis::Horizontal => (
+            UiRect {
+                left: Val::Percent(fraction * 100.0),
+                top: Val::Px(0.0),
+                ..default()
+            },
+            Size::new(Val::Px(thickness), Val::Percent(100.0)),
+            UiRect {
+                left: Val::Px(-thickness / 2.0),
+                ..default()
+            },
+        ),
+    };
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position,
+        size,
+        margin,
+        ..default()
+    };
+}
+
+// Sits next to its tick on the side of the bar facing the viewport.
+pub fn scroll_marker_tooltip_style(axis: ScrollAxis, side: ScrollBarSide) -> Style {
+    let mut position = UiRect::default();
+    match (axis, side) {
+        (ScrollAxis::Vertical, ScrollBarSide::Start) => position.left = Val::Percent(100.0),
+        (ScrollAxis::Vertical, ScrollBarSide::End) => position.right = Val::Percent(100.0),
+        (ScrollAxis::Horizontal, ScrollBarSide::Start) => position.top = Val::Percent(100.0),
+        (ScrollAxis::Horizontal, ScrollBarSide::End) => position.bottom = Val::Percent(100.0),
+    }
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position,
+        padding: UiRect::all(Val::Px(4.0)),
+        display: Display::None,
+        ..default()
+    };
+}
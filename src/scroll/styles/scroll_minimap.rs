@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+// Covers the bar beneath its handle.
+pub fn scroll_minimap_style() -> Style {
+    return Style {
+        position_type: PositionType::Absolute,
+        position: UiRect::all(Val::Px(0.0)),
+        overflow: Overflow::Hidden,
+        ..default()
+    };
+}
+
+// `rect` is in logical pixels from the top left of the bar.
+pub fn scroll_minimap_block_style(rect: Rect) -> Style {
+    return Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            left: Val::Px(rect.min.x),
+            top: Val::Px(rect.min.y),
+            ..default()
+        },
+        size: Size::new(Val::Px(rect.width()), Val::Px(rect.height())),
+        ..default()
+    };
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use crate::scroll::scroll_skin::ScrollSliceBorder;
+
+pub fn scroll_slice_root_style() -> Style {
+    return Style {
+        position_type: PositionType::Absolute,
+        position: UiRect::all(Val::Px(0.0)),
+        ..default()
+    };
+}
+
+// One of the nine clipping cells, `column` and `row` running 0 to 2. Corners keep the border
+// size, edges and the centre stretch with the skinned node.
+pub fn scroll_slice_cell_style(column: usize, row: usize, border: ScrollSliceBorder) -> Style {
+    let (left, right, width) = slice_cell_span(column, border.left, border.right);
+    let (top, bottom, height) = slice_cell_span(row, border.top, border.bottom);
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position: UiRect { left, right, top, bottom },
+        size: Size { width, height },
+        overflow: Overflow::Hidden,
+        ..default()
+    };
+}
+
+// The whole image inside a cell, sized and shifted so only that cell's slice shows through.
+pub fn scroll_slice_image_style(
+    column: usize,
+    row: usize,
+    border: ScrollSliceBorder,
+    image_size: Vec2,
+) -> Style {
+    let (left, right, width) = slice_image_span(column, border.left, border.right, image_size.x);
+    let (top, bottom, height) = slice_image_span(row, border.top, border.bottom, image_size.y);
+
+    return Style {
+        position_type: PositionType::Absolute,
+        position: UiRect { left, right, top, bottom },
+        size: Size { width, height },
+        ..default()
+    };
+}
+
+fn slice_cell_span(index: usize, start: f32, end: f32) -> (Val, Val, Val) {
+    match index {
+        0 => (Val::Px(0.0), Val::Undefined, Val::Px(start)),
+        1 => (Val::Px(start), Val::Px(end), Val::Auto),
+        _ => (Val::Undefined, Val::Px(0.0), Val::Px(end)),
+    }
+}
+
+// The middle slice is scaled as a share of its cell, which percentages resolve against.
+fn slice_image_span(index: usize, start: f32, end: f32, length: f32) -> (Val, Val, Val) {
+    let middle = (length - start - end).max(1.0);
+    match index {
+        0 => (Val::Px(0.0), Val::Undefined, Val::Px(length)),
+        1 => (
+            Val::Percent(-100.0 * start / middle),
+            Val::Undefined,
+            Val::Percent(100.0 * length / middle),
+        ),
+        _ => (Val::Undefined, Val::Px(0.0), Val::Px(length)),
+    }
+}
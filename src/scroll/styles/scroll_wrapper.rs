@@ -1,33 +1,19 @@
 use bevy::prelude::*;
 use crate::scroll::components::*;
 
+// Fills the frame; the container pushes it in from the sides that have outside bars. A
+// container that scrolls neither way just clips its content to the frame.
 pub fn scroll_wrapper_style(scroll_direction: ScrollDirection) -> Style {
     return Style {
-        min_size:          Size {
-            width: Val::Percent(30.0),
-            height: Val::Percent(30.0),
-        },
-        size: Size {
-            width: Val::Percent(30.0),
-            height: Val::Percent(30.0),
-        },
-        max_size: Size {
-            width: Val::Percent(30.0),
-            height: Val::Percent(30.0),
-        },
+        position_type: PositionType::Absolute,
+        position: UiRect::all(Val::Px(0.0)),
         flex_direction: match scroll_direction {
             ScrollDirection::Vertical => FlexDirection::Row,
             ScrollDirection::Horizontal => FlexDirection::Column,
-            ScrollDirection::Both => FlexDirection::Row,
-            ScrollDirection::Neither => todo!(),
+            ScrollDirection::Both | ScrollDirection::Neither => FlexDirection::Row,
         },
         overflow: Overflow::Hidden,
         align_items: AlignItems::Baseline,
-        align_self: AlignSelf::Center,
-        margin: UiRect {
-            left: Val::Percent(10.0),
-            ..default()
-        },
         ..default()
     };
 }
\ No newline at end of file